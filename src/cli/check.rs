@@ -0,0 +1,39 @@
+use std::fs::read_to_string;
+
+use chumsky::{extra::SimpleState, Parser as _};
+use yansi::Paint;
+
+use crate::parser::{lexer::lex, test_parser};
+
+use super::{
+    test::{files, print_error, print_type_error},
+    Options,
+};
+
+pub fn check(input_name: &Option<String>, options: &Options) {
+    let mut total = 0;
+    for input_name in files(input_name) {
+        let input = read_to_string(&input_name).unwrap();
+        let tokens = lex(&input);
+        let (test, errors) = test_parser()
+            .parse_with_state(tokens, &mut SimpleState::from(options.type_system()))
+            .into_output_errors();
+        for error in &errors {
+            print_error(&input, error, &input_name);
+        }
+        let Some(mut test) = test else {
+            continue;
+        };
+        let overlaps = test.ts.overlaps();
+        for error in &overlaps {
+            print_type_error(error, &input_name);
+        }
+        total += overlaps.len();
+    }
+    if total == 0 {
+        println!("{}", "No overlapping impls".green());
+    } else {
+        println!("{} overlapping impls", total.to_string().red());
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,32 @@
+use std::fs::read_to_string;
+
+use chumsky::{extra::SimpleState, Parser as _};
+
+use crate::{
+    logic::{explain::Explanation, Logic},
+    parser::{lexer::lex, test_parser},
+};
+
+use super::{test::print_error, Options};
+
+pub fn explain(input_name: &str, options: &Options) {
+    let input = read_to_string(input_name).unwrap();
+    let tokens = lex(&input);
+    let (test, errors) = test_parser()
+        .parse_with_state(tokens, &mut SimpleState::from(options.type_system()))
+        .into_output_errors();
+    for error in &errors {
+        print_error(&input, error, input_name);
+    }
+    let Some(test) = test else {
+        return;
+    };
+    let goals: Logic = test.goals.clone().into();
+    let explanation = goals.explain(&test.ts);
+    print!("{explanation}");
+    if matches!(explanation, Explanation::Fails(_)) && test.goals.len() > 1 {
+        if let Some(core) = test.ts.unsat_core(&test.goals) {
+            print!("{core}");
+        }
+    }
+}
@@ -1,7 +1,12 @@
 use tracing::Level;
 
+use crate::state::TypeSystem;
+
+pub mod check;
+pub mod explain;
 pub mod lex;
 pub mod repl;
+pub mod solve;
 pub mod test;
 
 #[derive(Debug, clap::Parser)]
@@ -15,10 +20,52 @@ pub enum Command {
         /// The minimum log level. If not provided, the default is `error`
         #[clap(short, long, default_value = "error")]
         log_level: Level,
+
+        #[clap(flatten)]
+        options: Options,
+    },
+
+    /// Check the impls in the current directory or a specific file for overlaps
+    Check {
+        /// File to check. If not provided, the current directory is used
+        #[clap(short, long)]
+        file: Option<String>,
+
+        #[clap(flatten)]
+        options: Options,
     },
 
     /// Start the REPL
-    Repl,
+    Repl {
+        /// The maximum number of solutions `solve` lists
+        #[clap(short = 'n', long, default_value = "10")]
+        limit: usize,
+
+        #[clap(flatten)]
+        options: Options,
+    },
+
+    /// List the solutions to the goals in a file
+    Solve {
+        /// The file to solve
+        file: String,
+
+        /// The maximum number of solutions to list
+        #[clap(short = 'n', long, default_value = "10")]
+        limit: usize,
+
+        #[clap(flatten)]
+        options: Options,
+    },
+
+    /// Print how the goals in a file are proven
+    Explain {
+        /// The file to explain
+        file: String,
+
+        #[clap(flatten)]
+        options: Options,
+    },
 
     /// Lex a file
     Lex {
@@ -27,12 +74,49 @@ pub enum Command {
     },
 }
 
+/// Settings for the type system, shared by every command which solves goals.
+#[derive(Debug, clap::Args)]
+pub struct Options {
+    /// How deep a path of impls can go before reporting an overflow
+    #[clap(long, default_value = "64")]
+    recursion_limit: usize,
+
+    /// Reject impls which overlap with an earlier impl
+    #[clap(long)]
+    deny_overlap: bool,
+
+    /// Prefer the most specific impl when more than one applies
+    #[clap(long)]
+    specialize: bool,
+}
+
+impl Options {
+    pub fn type_system(&self) -> TypeSystem {
+        let mut ts = TypeSystem::new();
+        ts.recursion_limit = self.recursion_limit;
+        ts.deny_overlap = self.deny_overlap;
+        ts.specialize = self.specialize;
+        ts
+    }
+}
+
 impl Command {
     pub fn run(&self) {
         match self {
-            Command::Test { file, log_level } => test::test(file, log_level),
+            Command::Test {
+                file,
+                log_level,
+                options,
+            } => test::test(file, log_level, options),
+            Command::Check { file, options } => check::check(file, options),
             Command::Lex { file } => lex::lex(file.to_string()),
-            Command::Repl => repl::repl().unwrap(),
+            Command::Repl { limit, options } => repl::repl(*limit, options).unwrap(),
+            Command::Solve {
+                file,
+                limit,
+                options,
+            } => solve::solve(file, *limit, options),
+            Command::Explain { file, options } => explain::explain(file, options),
         }
     }
 }
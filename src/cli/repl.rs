@@ -5,6 +5,7 @@ use pretty::BoxAllocator;
 use rustyline::{error::ReadlineError, DefaultEditor, Result};
 
 use crate::{
+    logic::Logic,
     parser::{
         lexer::lex,
         repl::{repl_parser, ListItem, ReplCommand},
@@ -12,11 +13,15 @@ use crate::{
     state::TypeSystem,
 };
 
-use super::test::print_error;
+use super::{
+    test::{print_error, print_type_error},
+    Options,
+};
 
-pub fn repl() -> Result<()> {
+/// Runs the REPL, with `solve` listing at most `limit` solutions.
+pub fn repl(limit: usize, options: &Options) -> Result<()> {
     let mut rl = DefaultEditor::new()?;
-    let mut state = SimpleState::from(TypeSystem::new());
+    let mut state = SimpleState::from(options.type_system());
     loop {
         let readline = rl.readline(">> ");
         match readline {
@@ -30,7 +35,10 @@ pub fn repl() -> Result<()> {
                     print_error(&line, error, "<input>");
                 }
                 if let Some(output) = output {
-                    output.handle(&mut state);
+                    output.handle(&mut state, limit);
+                }
+                for error in state.errors.drain(..) {
+                    print_type_error(&error, "<input>");
                 }
             }
             Err(ReadlineError::Interrupted) => {
@@ -50,7 +58,7 @@ pub fn repl() -> Result<()> {
 }
 
 impl ReplCommand {
-    pub fn handle(self, state: &mut TypeSystem) {
+    pub fn handle(self, state: &mut TypeSystem, limit: usize) {
         match self {
             ReplCommand::Goal(goal) => {
                 state.add_goal(goal);
@@ -62,16 +70,22 @@ impl ReplCommand {
                 println!();
             }
             ReplCommand::Eval(logic) => {
+                let reduced = logic.reduce(state, false);
                 let mut res = String::new();
-                logic
-                    .reduce(state, false)
+                reduced
                     .pretty::<_, ()>(&BoxAllocator)
                     .render_fmt(60, &mut res)
                     .unwrap();
                 println!("{}", res);
+                if reduced == Logic::False {
+                    for suggestion in logic.explain_step(state).suggestions() {
+                        println!("help: {suggestion}");
+                    }
+                }
             }
             ReplCommand::Impl => println!("Ok"),
             ReplCommand::Decl => println!("Ok"),
+            ReplCommand::Alias => println!("Ok"),
             ReplCommand::List(list_item) => match list_item {
                 ListItem::Decl => state.decls.values().for_each(|decl| println!("{decl}")),
                 ListItem::Impl(ident) => {
@@ -91,6 +105,18 @@ impl ReplCommand {
             },
             ReplCommand::New(ty) => println!("{}", ty.inst(state)),
             ReplCommand::Resolve(ty) => println!("{}", ty.resolve(state)),
+            ReplCommand::Join(first, second) => println!("{}", state.join(&first, &second)),
+            ReplCommand::Meet(first, second) => println!("{}", state.meet(&first, &second)),
+            ReplCommand::Solve(logic) => {
+                let solutions = logic.solutions(state, limit);
+                if solutions.is_empty() {
+                    println!("No solutions");
+                }
+                for solution in solutions {
+                    println!("{solution}");
+                }
+            }
+            ReplCommand::Explain(logic) => print!("{}", logic.explain(state)),
         }
     }
 }
@@ -1,7 +1,7 @@
 use crate::{
+    error::TypeError,
     logic::Logic,
     parser::{lexer::lex, test_parser},
-    state::TypeSystem,
 };
 use ariadne::{Color, Label, Report, ReportKind, Source};
 use chumsky::{extra::SimpleState, Parser as _};
@@ -10,26 +10,19 @@ use std::{fs::read_to_string, ops::Range};
 use tracing::Level;
 use yansi::Paint;
 
-pub fn test(input_name: &Option<String>, level: &Level) {
+use super::Options;
+
+pub fn test(input_name: &Option<String>, level: &Level, options: &Options) {
     let subscriber = tracing_subscriber::FmtSubscriber::builder()
         .with_max_level(*level)
         .finish();
     tracing::subscriber::set_global_default(subscriber).expect("Failed to set subscriber");
-    let files = if let Some(input_name) = input_name {
-        vec![input_name.to_string()]
-    } else {
-        let mut files = vec![];
-        for file in glob("**/*.type").unwrap() {
-            files.push(file.unwrap().to_str().unwrap().to_string());
-        }
-        files
-    };
     let mut results = vec![];
-    for input_name in files {
+    for input_name in files(input_name) {
         let input = read_to_string(&input_name).unwrap();
         let tokens = lex(&input);
         let (test, errors) = test_parser()
-            .parse_with_state(tokens, &mut SimpleState::from(TypeSystem::new()))
+            .parse_with_state(tokens, &mut SimpleState::from(options.type_system()))
             .into_output_errors();
         if !errors.is_empty() {
             println!("--- Failed to parse the input text for {} ---", input_name);
@@ -40,19 +33,27 @@ pub fn test(input_name: &Option<String>, level: &Level) {
         if !errors.is_empty() {
             println!("--- End of error ---");
         }
-        if let Some(test) = test {
-            let mut state = test.ts;
-            let mut goals: Logic = test.goals.into();
-            loop {
-                let next = goals.reduce(&mut state, true);
-                if next == goals {
-                    break;
-                }
-                goals = next;
+        // A test with errors in its input fails, even if the parser recovered.
+        if let Some(test) = test.filter(|_| errors.is_empty()) {
+            let mut state = test.ts.clone();
+            let goals = Logic::from(test.goals.clone()).settle(&mut state);
+            // Goals which got stuck only come to what they were stuck on, as
+            // `Unknown` would let them hold.
+            let goals = state.stuck().unwrap_or(goals);
+            for error in &state.errors {
+                print_type_error(error, &input_name);
             }
-            if goals == test.expected {
+            if goals.expand_aliases() == test.expected.expand_aliases() {
                 results.push((input_name, true));
             } else {
+                if goals == Logic::False {
+                    print!("{}", Logic::from(test.goals.clone()).explain(&test.ts));
+                    if test.goals.len() > 1 {
+                        if let Some(core) = test.ts.unsat_core(&test.goals) {
+                            print!("{core}");
+                        }
+                    }
+                }
                 results.push((input_name, false));
             }
         } else {
@@ -78,6 +79,23 @@ pub fn test(input_name: &Option<String>, level: &Level) {
     )
 }
 
+/// The given file, or every `.type` file under the current directory.
+pub fn files(input_name: &Option<String>) -> Vec<String> {
+    if let Some(input_name) = input_name {
+        vec![input_name.to_string()]
+    } else {
+        let mut files = vec![];
+        for file in glob("**/*.type").unwrap() {
+            files.push(file.unwrap().to_str().unwrap().to_string());
+        }
+        files
+    }
+}
+
+pub fn print_type_error(error: &TypeError, input_name: &str) {
+    println!("{}: {} ({})", "error".red(), error, input_name);
+}
+
 pub fn print_error(
     input: &str,
     error: &chumsky::prelude::Rich<'_, crate::parser::lexer::Token>,
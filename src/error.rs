@@ -0,0 +1,67 @@
+use thiserror::Error;
+
+use crate::{
+    logic::{stmt::Stmt, Logic},
+    ty::{bound::Bound, impl_::Impl, Type},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TypeError {
+    #[error("overflow evaluating requirement `{0}`")]
+    Overflow(Box<Stmt>),
+    #[error("`{0}` didn't settle within the recursion limit")]
+    Fixpoint(Box<Logic>),
+    #[error("conflicting impls `{first}` and `{second}` both apply to `{ty}`")]
+    Overlap {
+        first: Box<Impl>,
+        second: Box<Impl>,
+        ty: Type,
+    },
+    #[error("expected a type constructor taking {}, found `{ty}`", arguments(*.kind))]
+    Kind { ty: Type, kind: usize },
+    #[error("cannot find type `{0}`")]
+    Unknown(String),
+    #[error("`{name}` takes {} but {found} {} given", arity(*.min, *.max), if *.found == 1 { "was" } else { "were" })]
+    Arity {
+        name: String,
+        min: usize,
+        max: usize,
+        found: usize,
+    },
+    #[error("`{impl_}` doesn't satisfy `{bound}`, which `{head}` requires")]
+    Unsatisfied {
+        impl_: Box<Impl>,
+        head: String,
+        bound: Box<Bound>,
+    },
+    #[error("`{decl}` has no associated type `{name}`")]
+    UnknownAssoc { decl: String, name: String },
+    #[error("`{impl_}` doesn't bind the associated type `{name}`")]
+    MissingAssoc { impl_: Box<Impl>, name: String },
+    #[error("cannot infer {}, needed by {}", list(vars.iter().map(|id| format!("${id}"))), list(waiting.iter()))]
+    Stuck { vars: Vec<u32>, waiting: Vec<Stmt> },
+}
+
+/// Each item in backticks, separated by commas.
+fn list(items: impl Iterator<Item = impl std::fmt::Display>) -> String {
+    items
+        .map(|item| format!("`{item}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn arity(min: usize, max: usize) -> String {
+    if min == max {
+        arguments(min)
+    } else {
+        format!("{min} to {max} arguments")
+    }
+}
+
+fn arguments(count: usize) -> String {
+    if count == 1 {
+        "1 argument".to_string()
+    } else {
+        format!("{count} arguments")
+    }
+}
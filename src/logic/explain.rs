@@ -0,0 +1,802 @@
+use std::fmt::Display;
+
+use crate::{
+    state::TypeSystem,
+    ty::{impl_::Impl, select::Candidate, Type},
+};
+
+use super::{stmt::Stmt, suggest::Suggestion, Logic};
+
+/// What the reducer did, kept while explaining so proofs can be rebuilt afterwards.
+#[derive(Debug, Clone, Default)]
+pub struct Trace {
+    steps: Vec<Step>,
+    /// Candidates found for the statements still being reduced.
+    pending: Vec<Candidate>,
+    /// Paths rejected for the statements still being reduced.
+    rejected: Vec<Rejection>,
+    /// The statements reduced while reducing each statement still being reduced.
+    parts: Vec<Vec<Stmt>>,
+}
+
+/// A statement the reducer made progress on.
+#[derive(Debug, Clone)]
+struct Step {
+    goal: Stmt,
+    /// The impl paths which applied to the goal.
+    candidates: Vec<Candidate>,
+    /// The impl paths which didn't.
+    rejected: Vec<Rejection>,
+    /// The statements reduced on the way, such as the members of a union.
+    parts: Vec<Stmt>,
+    result: Logic,
+}
+
+/// How a statement was proven.
+#[derive(Debug, Clone)]
+pub struct Proof {
+    pub goal: Stmt,
+    pub rule: Rule,
+    /// The type vars bound while proving it.
+    pub bindings: Vec<(u32, Type)>,
+    pub children: Vec<Proof>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    /// By following a path of impls.
+    Impls(Vec<Impl>),
+    /// By a where-clause in scope.
+    WhereClause,
+    /// By leading back to a coinductive goal which is already being proven.
+    Cycle,
+    /// Directly, or by what its parts hold.
+    Holds,
+}
+
+/// Why a statement doesn't hold.
+#[derive(Debug, Clone)]
+pub struct Failure {
+    pub goal: Stmt,
+    pub reason: Reason,
+}
+
+#[derive(Debug, Clone)]
+pub enum Reason {
+    /// There is no path of impls to even try.
+    NoPath,
+    /// Every path of impls which was tried was rejected.
+    Rejected(Vec<Rejection>),
+    /// Some of its parts don't hold.
+    Parts(Vec<Failure>),
+    /// It leads back to itself, and isn't coinductive.
+    Cycle,
+    /// The types just don't match.
+    Mismatch,
+}
+
+/// A path of impls, and why it couldn't be used.
+#[derive(Debug, Clone)]
+pub struct Rejection {
+    pub path: Vec<Impl>,
+    pub why: Why,
+}
+
+#[derive(Debug, Clone)]
+pub enum Why {
+    /// The head of an impl on the path doesn't match the type reached so far.
+    Head { impl_: Box<Impl>, ty: Type },
+    /// The path leads to a type which doesn't match the one wanted.
+    Target(Type),
+    /// One of the where-clauses picked up on the path doesn't hold.
+    WhereClause(Box<Failure>),
+    /// It applies, but binds type vars differently to the other goals.
+    Conflict,
+}
+
+/// The result of explaining some goals.
+#[derive(Debug, Clone)]
+pub enum Explanation {
+    Holds(Vec<Proof>),
+    Fails(Vec<Failure>),
+    /// Neither, as the goals got stuck on what's left.
+    Stuck(Logic),
+}
+
+/// Where a statement's reduction started in a trace.
+#[derive(Debug, Clone, Copy)]
+pub struct Mark {
+    candidates: usize,
+    rejected: usize,
+}
+
+impl Trace {
+    pub fn enter(&mut self) -> Mark {
+        self.parts.push(vec![]);
+        Mark {
+            candidates: self.pending.len(),
+            rejected: self.rejected.len(),
+        }
+    }
+
+    pub fn candidates(&mut self, candidates: &[Candidate]) {
+        self.pending.extend_from_slice(candidates);
+    }
+
+    pub fn reject(&mut self, path: Vec<Impl>, why: Why) {
+        self.rejected.push(Rejection { path, why });
+    }
+
+    /// Records what was found since `start` as how `goal` reduced, if it made progress.
+    pub fn exit(&mut self, start: Mark, goal: Stmt, result: &Logic, progressed: bool) {
+        let parts = self.parts.pop().unwrap_or_default();
+        let candidates = self.pending.split_off(start.candidates);
+        let rejected = self.rejected.split_off(start.rejected);
+        if !progressed {
+            return;
+        }
+        if let Some(parent) = self.parts.last_mut() {
+            parent.push(goal.clone());
+        }
+        self.steps.push(Step {
+            goal,
+            candidates,
+            rejected,
+            parts,
+            result: result.clone(),
+        });
+    }
+
+    /// The latest step on `goal`, which is the one which went furthest.
+    fn step(&self, goal: &Stmt, state: &TypeSystem) -> Option<&Step> {
+        self.steps
+            .iter()
+            .rev()
+            .find(|step| &step.goal.resolve(state) == goal)
+    }
+
+    fn prove(&self, goal: &Stmt, state: &TypeSystem, ancestors: &mut Vec<Stmt>) -> Proof {
+        let goal = goal.resolve(state);
+        if ancestors.contains(&goal) {
+            return Proof::leaf(goal, Rule::Cycle);
+        }
+        let Some(step) = self.step(&goal, state) else {
+            return Proof::leaf(goal, Rule::Holds);
+        };
+        ancestors.push(goal.clone());
+        let applied = step
+            .candidates
+            .iter()
+            .find(|candidate| candidate.agrees(state));
+        let children = if let Some(candidate) = applied {
+            candidate
+                .bounds
+                .iter()
+                .cloned()
+                .flat_map(|bound| Logic::from(bound).stmts())
+                .collect()
+        } else if !step.parts.is_empty() {
+            // Only the alternatives which held, when just one of them had to.
+            step.parts
+                .iter()
+                .filter(|part| part.holds(state))
+                .cloned()
+                .collect()
+        } else {
+            step.result.stmts()
+        };
+        let children = children
+            .iter()
+            .map(|child| self.prove(child, state, ancestors))
+            .collect();
+        ancestors.pop();
+        let rule = match (applied, &goal) {
+            (Some(candidate), _) if !candidate.path.is_empty() => {
+                Rule::Impls(candidate.path.clone())
+            }
+            (
+                None,
+                Stmt::Extends {
+                    sub: Type::Generic(..),
+                    ..
+                },
+            ) => Rule::WhereClause,
+            _ => Rule::Holds,
+        };
+        let bindings = Logic::Stmt(step.goal.clone())
+            .vars()
+            .into_iter()
+            .filter_map(|id| {
+                state
+                    .resolve(id)
+                    .map(|_| (id, Type::Var(id).resolve(state)))
+            })
+            .collect();
+        Proof {
+            goal,
+            rule,
+            bindings,
+            children,
+        }
+    }
+}
+
+impl Trace {
+    fn fails(&self, goal: &Stmt, state: &TypeSystem) -> bool {
+        self.step(&goal.resolve(state), state)
+            .is_some_and(|step| step.result == Logic::False)
+    }
+
+    fn fail(&self, goal: &Stmt, state: &TypeSystem, ancestors: &mut Vec<Stmt>) -> Failure {
+        let goal = goal.resolve(state);
+        if ancestors.contains(&goal) {
+            return Failure {
+                goal,
+                reason: Reason::Cycle,
+            };
+        }
+        let Some(step) = self.step(&goal, state) else {
+            return Failure {
+                goal,
+                reason: Reason::Mismatch,
+            };
+        };
+        ancestors.push(goal.clone());
+        let mut rejected = step.rejected.clone();
+        for candidate in &step.candidates {
+            let failed = candidate
+                .bounds
+                .iter()
+                .cloned()
+                .flat_map(|bound| Logic::from(bound).stmts())
+                .find(|bound| self.fails(bound, state));
+            let why = match failed {
+                Some(bound) => Why::WhereClause(Box::new(self.fail(&bound, state, ancestors))),
+                None => Why::Conflict,
+            };
+            rejected.push(Rejection {
+                path: candidate.path.clone(),
+                why,
+            });
+        }
+        // The parts are only looked at when no path was tried, as otherwise they're
+        // the where-clauses which were just explained.
+        let parts = if rejected.is_empty() {
+            step.parts
+                .iter()
+                .filter(|part| self.fails(part, state))
+                .map(|part| self.fail(part, state, ancestors))
+                .collect::<Vec<_>>()
+        } else {
+            vec![]
+        };
+        ancestors.pop();
+        let reason = if !rejected.is_empty() {
+            Reason::Rejected(rejected)
+        } else if !parts.is_empty() {
+            Reason::Parts(parts)
+        } else if goal.searches_impls() {
+            Reason::NoPath
+        } else {
+            Reason::Mismatch
+        };
+        Failure { goal, reason }
+    }
+}
+
+impl Stmt {
+    /// Whether reducing this looks for paths of impls.
+    fn searches_impls(&self) -> bool {
+        match self {
+            Stmt::Extends { sub, super_ } => matches!(
+                (sub.unalias(), super_.unalias()),
+                (Type::Named(_) | Type::Var(_), Type::Named(_))
+            ),
+            Stmt::HasMember { .. } => true,
+            Stmt::Exactly { .. } => false,
+        }
+    }
+
+    /// Whether this holds with the type vars as they ended up bound, which the
+    /// alternatives which weren't taken don't.
+    fn holds(&self, state: &TypeSystem) -> bool {
+        let mut state = state.clone();
+        state.trace = None;
+        Logic::Stmt(self.resolve(&state)).reduce_to_fixpoint(&mut state, false) == Logic::True
+    }
+}
+
+impl Candidate {
+    /// Whether the bindings this candidate needs are the ones which were made.
+    fn agrees(&self, state: &TypeSystem) -> bool {
+        self.bindings
+            .iter()
+            .all(|(id, ty)| Type::Var(*id).resolve(state) == ty.resolve(state))
+    }
+}
+
+impl Logic {
+    /// Settles the goals with tracing on, then rebuilds how each statement in
+    /// them was proven, or why the ones which failed don't hold.
+    ///
+    /// When settling gets stuck on a choice between alternatives, the first
+    /// alternative of each choice is taken and explained instead.
+    pub fn explain(&self, state: &TypeSystem) -> Explanation {
+        let mut settled = state.clone();
+        settled.trace = Some(Trace::default());
+        let res = self.settle(&mut settled);
+        // A proof which leans on `Unknown` proves nothing.
+        let Some(stuck) = settled.stuck() else {
+            return self.explanation(res, settled);
+        };
+        let mut state = state.clone();
+        state.trace = Some(Trace::default());
+        let mut logic = self.reduce_to_fixpoint(&mut state, true);
+        if !logic.has_choice() {
+            return Explanation::Stuck(stuck);
+        }
+        for _ in 0..state.recursion_limit {
+            if !logic.has_choice() {
+                break;
+            }
+            logic = logic.first_choice().reduce_to_fixpoint(&mut state, true);
+        }
+        let res = logic.settle(&mut state);
+        match state.stuck() {
+            Some(stuck) => Explanation::Stuck(stuck),
+            None => self.explanation(res, state),
+        }
+    }
+
+    /// Explains a single reduction without inference, which is how the REPL
+    /// evaluates a goal.
+    pub fn explain_step(&self, state: &TypeSystem) -> Explanation {
+        let mut state = state.clone();
+        state.trace = Some(Trace::default());
+        let res = self.reduce(&mut state, false);
+        self.explanation(res, state)
+    }
+
+    /// Rebuilds what the trace in `state` says about how the goals came to `res`.
+    fn explanation(&self, res: Logic, mut state: TypeSystem) -> Explanation {
+        let trace = state.trace.take().unwrap();
+        let stmts = self.stmts();
+        match res {
+            // Alternatives which weren't taken don't need proving.
+            Logic::True => Explanation::Holds(
+                stmts
+                    .iter()
+                    .filter(|stmt| stmt.holds(&state))
+                    .map(|stmt| trace.prove(stmt, &state, &mut vec![]))
+                    .collect(),
+            ),
+            Logic::False => {
+                let failed = stmts
+                    .iter()
+                    .filter(|stmt| trace.fails(stmt, &state))
+                    .collect::<Vec<_>>();
+                // When no single statement failed, it was them together which did.
+                let failed = if failed.is_empty() {
+                    stmts.iter().collect()
+                } else {
+                    failed
+                };
+                Explanation::Fails(
+                    failed
+                        .into_iter()
+                        .map(|stmt| trace.fail(stmt, &state, &mut vec![]))
+                        .collect(),
+                )
+            }
+            res => Explanation::Stuck(res.resolve(&state)),
+        }
+    }
+
+    fn has_choice(&self) -> bool {
+        match self {
+            Logic::OneOf(_) => true,
+            Logic::AllOf(logics) => logics.iter().any(Logic::has_choice),
+            _ => false,
+        }
+    }
+
+    /// These goals with their first choice made for the first alternative.
+    fn first_choice(&self) -> Logic {
+        match self {
+            Logic::OneOf(logics) => logics[0].clone(),
+            Logic::AllOf(logics) => {
+                let mut logics = logics.clone();
+                if let Some(logic) = logics.iter_mut().find(|logic| logic.has_choice()) {
+                    *logic = logic.first_choice();
+                }
+                Logic::AllOf(logics)
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// The impls on the paths which were taken while reducing the goals, whether
+    /// or not they hold in the end.
+    pub fn impls_used(&self, state: &TypeSystem) -> Vec<Impl> {
+        let mut state = state.clone();
+        state.trace = Some(Trace::default());
+        self.settle(&mut state);
+        let trace = state.trace.take().unwrap();
+        let mut impls = vec![];
+        for step in &trace.steps {
+            let Some(candidate) = step
+                .candidates
+                .iter()
+                .find(|candidate| candidate.agrees(&state))
+            else {
+                continue;
+            };
+            for impl_ in &candidate.path {
+                if !impls.contains(impl_) {
+                    impls.push(impl_.clone());
+                }
+            }
+        }
+        impls
+    }
+}
+
+impl Proof {
+    fn leaf(goal: Stmt, rule: Rule) -> Proof {
+        Proof {
+            goal,
+            rule,
+            bindings: vec![],
+            children: vec![],
+        }
+    }
+
+    fn fmt_indented(&self, f: &mut std::fmt::Formatter<'_>, depth: usize) -> std::fmt::Result {
+        write!(f, "{}{}", "  ".repeat(depth), self.goal)?;
+        match &self.rule {
+            Rule::Impls(path) => write!(f, " by {}", show_path(path))?,
+            Rule::WhereClause => write!(f, " by a where-clause")?,
+            Rule::Cycle => write!(f, " by a coinductive cycle")?,
+            Rule::Holds => {}
+        }
+        if !self.bindings.is_empty() {
+            write!(
+                f,
+                " with {}",
+                self.bindings
+                    .iter()
+                    .map(|(id, ty)| format!("${id} = {ty}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
+        }
+        writeln!(f)?;
+        for child in &self.children {
+            child.fmt_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Proof {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+impl Failure {
+    fn fmt_indented(&self, f: &mut std::fmt::Formatter<'_>, depth: usize) -> std::fmt::Result {
+        let indent = "  ".repeat(depth + 1);
+        writeln!(f, "{}{} doesn't hold", "  ".repeat(depth), self.goal)?;
+        match &self.reason {
+            Reason::NoPath => match &self.goal {
+                Stmt::Extends { sub, super_ } => {
+                    writeln!(f, "{indent}no impl leads from {sub} to {super_}")
+                }
+                Stmt::HasMember { ty, member, .. } => {
+                    writeln!(f, "{indent}no impl gives {ty} a member {member}")
+                }
+                Stmt::Exactly { .. } => writeln!(f, "{indent}the types don't match"),
+            },
+            Reason::Rejected(rejected) => {
+                for rejection in rejected {
+                    write!(
+                        f,
+                        "{indent}{} was rejected, as ",
+                        show_path(&rejection.path)
+                    )?;
+                    match &rejection.why {
+                        Why::Head { impl_, ty } => {
+                            writeln!(f, "the head {} of {impl_} doesn't match {ty}", impl_.from)?
+                        }
+                        Why::Target(ty) => writeln!(f, "it leads to {ty}, which doesn't match")?,
+                        Why::WhereClause(failure) => {
+                            writeln!(f, "one of its where-clauses doesn't hold")?;
+                            failure.fmt_indented(f, depth + 2)?;
+                        }
+                        Why::Conflict => {
+                            writeln!(f, "it binds type vars differently to the other goals")?
+                        }
+                    }
+                }
+                Ok(())
+            }
+            Reason::Parts(parts) => {
+                for part in parts {
+                    part.fmt_indented(f, depth + 1)?;
+                }
+                Ok(())
+            }
+            Reason::Cycle => writeln!(f, "{indent}it depends on itself"),
+            Reason::Mismatch => writeln!(f, "{indent}the types don't match"),
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Explanation::Holds(proofs) => proofs.iter().try_for_each(|proof| write!(f, "{proof}")),
+            Explanation::Fails(failures) => {
+                for failure in failures {
+                    write!(f, "{failure}")?;
+                }
+                for suggestion in self.suggestions() {
+                    writeln!(f, "help: {suggestion}")?;
+                }
+                Ok(())
+            }
+            Explanation::Stuck(logic) => writeln!(f, "The goals are stuck on {logic}"),
+        }
+    }
+}
+
+impl Explanation {
+    /// The suggestions for every failure, without repeats.
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        let Explanation::Fails(failures) = self else {
+            return vec![];
+        };
+        let mut suggestions = vec![];
+        for suggestion in failures.iter().flat_map(Failure::suggestions) {
+            if !suggestions.contains(&suggestion) {
+                suggestions.push(suggestion);
+            }
+        }
+        suggestions
+    }
+}
+
+fn show_path(path: &[Impl]) -> String {
+    path.iter()
+        .map(Impl::to_string)
+        .collect::<Vec<_>>()
+        .join(", then ")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{logic::Logic, parser::parse_test};
+
+    use super::{Explanation, Rule};
+
+    fn explain(input: &str) -> Explanation {
+        let test = parse_test(input);
+        let goals: Logic = test.goals.into();
+        goals.explain(&test.ts)
+    }
+
+    #[test]
+    fn test_where_clause_is_proven() {
+        let proofs = explain(
+            "
+            Int;
+            List[T];
+            Display;
+            impl [] Display for Int;
+            impl [T] Display for List[T] where T: Display;
+            ---
+            List[$0]: Display;
+            $0 = Int;
+            ---
+            true
+            ",
+        );
+        let Explanation::Holds(proofs) = proofs else {
+            panic!("Expected proofs");
+        };
+        assert_eq!(
+            proofs[0].to_string(),
+            "List[Int]: Display by impl [T] Display for List[T] with $0 = Int\n  \
+             Int: Display by impl [] Display for Int\n"
+        );
+        assert_eq!(proofs[1].rule, Rule::Holds);
+    }
+
+    #[test]
+    fn test_first_solution_is_explained() {
+        let proofs = explain(
+            "
+            Int;
+            Bool;
+            Display;
+            impl [] Display for Int;
+            impl [] Display for Bool;
+            ---
+            $0 = Int | $0 = Bool;
+            $0: Display;
+            ---
+            true
+            ",
+        );
+        let Explanation::Holds(proofs) = proofs else {
+            panic!("Expected proofs, found {proofs}");
+        };
+        assert_eq!(proofs.len(), 2);
+        assert_eq!(
+            proofs[1].to_string(),
+            "Int: Display by impl [] Display for Int\n"
+        );
+    }
+
+    #[test]
+    fn test_first_solution_through_impl_vars() {
+        let proofs = explain(
+            "
+            Bool;
+            List[T];
+            Box[T];
+            Into[T];
+            impl [T] Into[List[T]] for Box[T];
+            ---
+            Box[$1]: Into[$0] | $0 = Bool;
+            ---
+            true
+            ",
+        );
+        let Explanation::Holds(proofs) = proofs else {
+            panic!("Expected proofs, found {proofs}");
+        };
+        assert_eq!(proofs.len(), 1);
+        assert!(matches!(proofs[0].rule, Rule::Impls(_)));
+    }
+
+    #[test]
+    fn test_impl_vars_left_free() {
+        let explanation = explain(
+            "
+            Int;
+            Bool;
+            Display;
+            List[T] { x: Int };
+            impl [T] Display for List[T];
+            impl [] Display for Int;
+            ---
+            $0: Display;
+            $0 has x: Int;
+            $1 = Int | $1 = Bool;
+            ---
+            true
+            ",
+        );
+        // Nothing pins down `List`'s argument, so there's nothing to prove.
+        assert!(matches!(explanation, Explanation::Stuck(_)));
+    }
+
+    #[test]
+    fn test_rejected_alternative_isnt_proven() {
+        let proofs = explain(
+            "
+            Bool;
+            List[T];
+            Display;
+            impl [T] Display for List[T];
+            ---
+            $0: Display;
+            $0 = List[$1] | $0 = Bool;
+            ---
+            true
+            ",
+        );
+        let Explanation::Holds(proofs) = proofs else {
+            panic!("Expected proofs, found {proofs}");
+        };
+        assert!(proofs
+            .iter()
+            .all(|proof| !proof.to_string().contains("Bool")));
+    }
+
+    #[test]
+    fn test_fallbacks_are_explained() {
+        let proofs = explain(include_str!("../../tests/fallback.type"));
+        let Explanation::Holds(proofs) = proofs else {
+            panic!("Expected proofs, found {proofs}");
+        };
+        assert!(proofs[3]
+            .to_string()
+            .starts_with("Pair[Int, Unit]: Pinned by impl [] Pinned for Pair[Int, Unit]"));
+    }
+
+    #[test]
+    fn test_growing_where_clause() {
+        let explanation = explain(
+            "
+            Z;
+            Nat[T];
+            Foo;
+            impl [T] Foo for Nat[T] where Nat[Nat[T]]: Foo;
+            ---
+            Nat[Z]: Foo;
+            ---
+            false
+            ",
+        );
+        let Explanation::Fails(failures) = explanation else {
+            panic!("Expected failures, found {explanation}");
+        };
+        // Each level is explained once, down to the recursion limit, rather
+        // than once for each way of reaching it.
+        let text = failures[0].to_string();
+        assert_eq!(text.matches("Foo doesn't hold").count(), 65);
+    }
+
+    #[test]
+    fn test_step_doesnt_infer() {
+        let test = parse_test(
+            "
+            Int;
+            Foo;
+            List[T];
+            Display;
+            impl [] Display for List[Int];
+            ---
+            List[$0]: Display;
+            ---
+            true
+            ",
+        );
+        let goals: Logic = test.goals.into();
+        assert!(matches!(goals.explain(&test.ts), Explanation::Holds(_)));
+        assert!(matches!(
+            goals.explain_step(&test.ts),
+            Explanation::Stuck(_)
+        ));
+    }
+
+    #[test]
+    fn test_rejected_candidates() {
+        let explanation = explain(
+            "
+            Int;
+            Foo;
+            List[T];
+            Display;
+            impl [] Display for Int;
+            impl [] Display for List[Int];
+            impl [T] Display for List[T] where T: Display;
+            ---
+            List[Foo]: Display;
+            ---
+            false
+            ",
+        );
+        assert_eq!(
+            explanation.to_string(),
+            "List[Foo]: Display doesn't hold\n  \
+             impl [] Display for List[Int] was rejected, as the head List[Int] of \
+             impl [] Display for List[Int] doesn't match List[Foo]\n  \
+             impl [T] Display for List[T] was rejected, as one of its where-clauses doesn't hold\n    \
+             Foo: Display doesn't hold\n      \
+             no impl leads from Foo to Display\n\
+             help: `impl [T] Display for List[T]` applies if `Foo: Display`\n\
+             help: add `impl [] Display for Foo`\n"
+        );
+    }
+}
@@ -3,10 +3,15 @@ use std::fmt::Display;
 use stmt::Stmt;
 use tracing::info;
 
-use crate::state::TypeSystem;
+use crate::{state::TypeSystem, ty::Type};
 
+pub mod explain;
 pub mod pretty;
+pub mod search;
+pub mod solve;
 pub mod stmt;
+pub mod suggest;
+pub mod unsat;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Logic {
@@ -52,6 +57,7 @@ impl Display for Logic {
 impl Logic {
     pub fn reduce(&self, state: &mut TypeSystem, infer: bool) -> Logic {
         let res = match self {
+            Logic::OneOf(logics) if infer => Logic::search(logics, &[], state),
             Logic::OneOf(logics) => {
                 let total = logics.len();
                 let reduced = logics
@@ -76,11 +82,28 @@ impl Logic {
                     Logic::OneOf(reduced)
                 }
             }
-            Logic::AllOf(logics) => logics
-                .iter()
-                .map(|l| l.reduce(state, infer))
-                .collect::<Vec<_>>()
-                .into(),
+            Logic::AllOf(logics) => {
+                let reduced = logics
+                    .iter()
+                    .map(|l| l.reduce(state, infer))
+                    .collect::<Vec<_>>()
+                    .into();
+                match reduced {
+                    // Branch on the first choice, so each alternative is checked against the rest.
+                    Logic::AllOf(mut logics) if infer => {
+                        match logics.iter().position(|l| matches!(l, Logic::OneOf(_))) {
+                            Some(index) => {
+                                let Logic::OneOf(alternatives) = logics.remove(index) else {
+                                    unreachable!()
+                                };
+                                Logic::search(&alternatives, &logics, state)
+                            }
+                            None => Logic::AllOf(logics),
+                        }
+                    }
+                    reduced => reduced,
+                }
+            }
             Logic::Stmt(stmt) => stmt.reduce(state, infer),
             Logic::True | Logic::False => self.clone(),
         };
@@ -88,6 +111,56 @@ impl Logic {
         res
     }
 
+    /// A type var being bound to a type.
+    pub fn binding(id: u32, ty: Type) -> Logic {
+        Logic::Stmt(Stmt::Exactly {
+            ty: Type::Var(id),
+            is: ty,
+        })
+    }
+
+    pub fn resolve(&self, state: &TypeSystem) -> Logic {
+        match self {
+            Logic::OneOf(logics) => Logic::OneOf(logics.iter().map(|l| l.resolve(state)).collect()),
+            Logic::AllOf(logics) => Logic::AllOf(logics.iter().map(|l| l.resolve(state)).collect()),
+            Logic::Stmt(stmt) => Logic::Stmt(stmt.resolve(state)),
+            Logic::True | Logic::False => self.clone(),
+        }
+    }
+
+    pub fn expand_aliases(&self) -> Logic {
+        match self {
+            Logic::OneOf(logics) => {
+                Logic::OneOf(logics.iter().map(Logic::expand_aliases).collect())
+            }
+            Logic::AllOf(logics) => {
+                Logic::AllOf(logics.iter().map(Logic::expand_aliases).collect())
+            }
+            Logic::Stmt(stmt) => Logic::Stmt(stmt.expand_aliases()),
+            Logic::True | Logic::False => self.clone(),
+        }
+    }
+
+    /// Every statement in this logic.
+    pub fn stmts(&self) -> Vec<Stmt> {
+        match self {
+            Logic::OneOf(logics) | Logic::AllOf(logics) => {
+                logics.iter().flat_map(Logic::stmts).collect()
+            }
+            Logic::Stmt(stmt) => vec![stmt.clone()],
+            Logic::True | Logic::False => vec![],
+        }
+    }
+
+    /// At least one of `logics`, collapsing the trivial cases.
+    pub fn any(mut logics: Vec<Logic>) -> Logic {
+        match logics.len() {
+            0 => Logic::False,
+            1 => logics.pop().unwrap(),
+            _ => Logic::OneOf(logics),
+        }
+    }
+
     // TODO: Implement this, but also think of XOR the is reserved in OneOf
     // pub fn union(&self, other: &Logic) -> Logic {
     //     match (self, other) {
@@ -57,7 +57,14 @@ impl Stmt {
                 .text(sub.to_string())
                 .append(allocator.text(": "))
                 .append(super_.to_string()),
-            Stmt::HasMember { .. } => todo!(),
+            Stmt::HasMember {
+                ty,
+                member,
+                member_ty,
+            } => allocator
+                .text(ty.to_string())
+                .append(allocator.text(format!(" has {member}: ")))
+                .append(member_ty.to_string()),
         }
     }
 }
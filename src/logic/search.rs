@@ -0,0 +1,324 @@
+use std::collections::HashMap;
+
+use crate::{error::TypeError, state::TypeSystem, ty::Type};
+
+use super::Logic;
+
+/// An alternative which held up alongside everything around it.
+struct Solution {
+    logic: Logic,
+    /// What's left of the alternative itself, without the rest.
+    own: Logic,
+    bindings: Vec<(u32, Type)>,
+    type_vars: HashMap<u32, Type>,
+}
+
+impl Logic {
+    /// Reduces until nothing changes, giving up with an error after
+    /// `TypeSystem::recursion_limit` rounds.
+    pub fn reduce_to_fixpoint(&self, state: &mut TypeSystem, infer: bool) -> Logic {
+        let mut logic = self.clone();
+        for _ in 0..state.recursion_limit {
+            let next = logic.reduce(state, infer);
+            if next == logic {
+                return next;
+            }
+            logic = next;
+        }
+        state.error(TypeError::Fixpoint(Box::new(logic.clone())));
+        logic
+    }
+
+    /// Reduces to a fixpoint, and whenever that leaves statements waiting on free
+    /// type vars, binds them by the fallbacks which apply and carries on. Those
+    /// no fallback applies to are set to `Unknown`, with an error.
+    pub fn settle(&self, state: &mut TypeSystem) -> Logic {
+        let mut logic = self.reduce_to_fixpoint(state, true);
+        loop {
+            if state.apply_fallbacks(&logic) {
+                logic = logic.reduce_to_fixpoint(state, true);
+                continue;
+            }
+            let vars = logic
+                .resolve(state)
+                .vars()
+                .into_iter()
+                .filter(|id| state.resolve(*id).is_none())
+                .collect::<Vec<_>>();
+            if vars.is_empty() {
+                return logic;
+            }
+            let waiting = logic
+                .stmts()
+                .into_iter()
+                .map(|stmt| stmt.resolve(state))
+                .filter(|stmt| {
+                    Logic::Stmt(stmt.clone())
+                        .vars()
+                        .iter()
+                        .any(|id| vars.contains(id))
+                })
+                .collect();
+            for id in &vars {
+                state.type_vars.insert(*id, Type::Unknown);
+            }
+            state.error(TypeError::Stuck { vars, waiting });
+            logic = logic.reduce_to_fixpoint(state, true);
+        }
+    }
+
+    /// Tries each alternative together with `rest`, with inference on, rolling the
+    /// type vars back after each one.
+    ///
+    /// If only one alternative is consistent it is committed to, otherwise all of
+    /// the consistent ones are kept, each with the bindings it would make.
+    pub fn search(alternatives: &[Logic], rest: &[Logic], state: &mut TypeSystem) -> Logic {
+        let mut solutions = vec![];
+        for alternative in alternatives {
+            let snapshot = state.snapshot();
+            let mut logics = rest.to_vec();
+            logics.push(alternative.clone());
+            let reduced = Logic::AllOf(logics).reduce_to_fixpoint(state, true);
+            let logic = reduced.resolve(state);
+            let bindings = state.bindings_since(&snapshot);
+            // Alternatives which come to the same thing, like two impls which both
+            // apply, aren't a choice.
+            let seen = solutions.iter().any(|solution: &Solution| {
+                solution.logic == logic && solution.bindings == bindings
+            });
+            if reduced != Logic::False && !seen {
+                let own = alternative.reduce_to_fixpoint(state, true).resolve(state);
+                solutions.push(Solution {
+                    logic,
+                    own,
+                    bindings,
+                    type_vars: state.snapshot(),
+                });
+            }
+            state.restore(snapshot);
+        }
+        match solutions.len() {
+            0 => Logic::False,
+            1 => {
+                let solution = solutions.pop().unwrap();
+                state.restore(solution.type_vars);
+                solution.logic
+            }
+            _ => {
+                state.revive(solutions.iter().flat_map(|solution| {
+                    let mut vars = solution.logic.vars();
+                    for (_, ty) in &solution.bindings {
+                        vars.extend(ty.vars());
+                    }
+                    vars
+                }));
+                let choice = solutions
+                    .into_iter()
+                    .map(|solution| {
+                        let mut logics = solution
+                            .bindings
+                            .into_iter()
+                            .map(|(id, ty)| Logic::binding(id, ty))
+                            .collect::<Vec<_>>();
+                        logics.push(solution.own);
+                        logics.into()
+                    })
+                    .collect();
+                // Each solution's `logic` has its own copy of the rest, so the rest is
+                // kept once, outside the choice.
+                let mut logics = rest.to_vec();
+                logics.push(Logic::OneOf(choice));
+                logics.into()
+            }
+        }
+    }
+}
+
+impl TypeSystem {
+    /// The statements settling got stuck on, when it had to set type vars to
+    /// `Unknown` to carry on. The goals don't hold, whatever they reduced to.
+    pub fn stuck(&self) -> Option<Logic> {
+        let waiting = self
+            .errors
+            .iter()
+            .flat_map(|error| match error {
+                TypeError::Stuck { waiting, .. } => waiting.clone(),
+                _ => vec![],
+            })
+            .map(Logic::Stmt)
+            .collect::<Vec<_>>();
+        (!waiting.is_empty()).then(|| Logic::from(waiting))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{error::TypeError, logic::Logic, parser::parse_test, ty::Type};
+
+    #[test]
+    fn test_choice_alone() {
+        let input = "
+            Int;
+            Bool;
+            ---
+            $0 = Int | $0 = Bool;
+            ---
+            true
+        ";
+        let test = parse_test(input);
+        let mut ts = test.ts;
+        let Some(Logic::OneOf(alternatives)) = test.goals.first() else {
+            panic!("Expected OneOf");
+        };
+        // With nothing else to hold, the choice isn't wrapped in an `AllOf`.
+        assert!(matches!(
+            Logic::search(alternatives, &[], &mut ts),
+            Logic::OneOf(choice) if choice.len() == 2
+        ));
+    }
+
+    #[test]
+    fn test_specializing_keeps_written_choices() {
+        let input = "
+            Int;
+            ---
+            $0 = Int | Int = Int;
+            ---
+            true
+        ";
+        let test = parse_test(input);
+        let mut ts = test.ts;
+        ts.specialize = true;
+        let Some(Logic::OneOf(alternatives)) = test.goals.first() else {
+            panic!("Expected OneOf");
+        };
+        // Specializing chooses between impls, not between the goals' own alternatives.
+        assert!(matches!(
+            Logic::search(alternatives, &[], &mut ts),
+            Logic::OneOf(choice) if choice.len() == 2
+        ));
+    }
+
+    #[test]
+    fn test_choice_beside_stuck_goals() {
+        let input = "
+            Int;
+            Bool;
+            Display;
+            List[T] { x: Int };
+            impl [T] Display for List[T];
+            impl [] Display for Int;
+            ---
+            $0: Display;
+            $0 has x: Int;
+            $1 = Int | $1 = Bool;
+            ---
+            true
+        ";
+        let test = parse_test(input);
+        let mut ts = test.ts;
+        let goals = Logic::from(test.goals.clone());
+        let reduced = goals.reduce_to_fixpoint(&mut ts, true);
+        assert!(ts.errors.is_empty());
+        // The goals beside the choice are kept once, not copied into each alternative.
+        assert_eq!(reduced.to_string().matches("has x").count(), 1);
+    }
+
+    #[test]
+    fn test_choice_through_impl_vars() {
+        let input = "
+            Bool;
+            List[T];
+            Box[T];
+            Into[T];
+            impl [T] Into[List[T]] for Box[T];
+            ---
+            Box[$1]: Into[$0] | $0 = Bool;
+            ---
+            true
+        ";
+        let test = parse_test(input);
+        let mut ts = test.ts;
+        let goals = Logic::from(test.goals.clone());
+        // Nothing is left of the impl's own vars to pile up round after round.
+        assert!(matches!(
+            goals.reduce_to_fixpoint(&mut ts, true),
+            Logic::OneOf(choice) if choice.len() == 2
+        ));
+        assert!(ts.errors.is_empty());
+    }
+
+    #[test]
+    fn test_same_alternatives() {
+        let input = "
+            Int;
+            ---
+            $0 = Int | $0 = Int;
+            ---
+            true
+        ";
+        let test = parse_test(input);
+        let mut ts = test.ts;
+        let Some(Logic::OneOf(alternatives)) = test.goals.first() else {
+            panic!("Expected OneOf");
+        };
+        assert_eq!(Logic::search(alternatives, &[], &mut ts), Logic::True);
+        assert_eq!(
+            ts.resolve(0).map(|ty| ty.to_string()),
+            Some("Int".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fixpoint_limit() {
+        let input = "
+            Int;
+            Display;
+            impl [] Display for Int;
+            ---
+            $0: Display & $0 = $1 & $1 = $2 & $2 = Int;
+            ---
+            true
+        ";
+        let test = parse_test(input);
+        let goals = Logic::from(test.goals.clone());
+
+        let mut ts = test.ts.clone();
+        assert_eq!(goals.reduce_to_fixpoint(&mut ts, true), Logic::True);
+        assert!(ts.errors.is_empty());
+
+        let mut ts = test.ts;
+        ts.recursion_limit = 1;
+        assert_ne!(goals.reduce_to_fixpoint(&mut ts, true), Logic::True);
+        assert!(matches!(&ts.errors[..], [TypeError::Fixpoint(_)]));
+    }
+
+    #[test]
+    fn test_stuck_var_becomes_unknown() {
+        let input = "
+            Int;
+            Bool;
+            Display;
+            impl [] Display for Int;
+            impl [] Display for Bool;
+            ---
+            $0: Display;
+            ---
+            true
+        ";
+        let test = parse_test(input);
+        let mut ts = test.ts;
+        let goals = Logic::from(test.goals.clone());
+        assert_eq!(goals.settle(&mut ts), Logic::True);
+        assert_eq!(ts.resolve(0), Some(Type::Unknown));
+        assert!(matches!(
+            &ts.errors[..],
+            [TypeError::Stuck { vars, waiting }] if vars == &[0] && waiting.len() == 1
+        ));
+        assert_eq!(
+            ts.stuck().map(|stuck| stuck.to_string()),
+            Some("$0: Display".into())
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use std::fmt::Display;
+
+use crate::{
+    state::TypeSystem,
+    ty::{select::Candidate, Type},
+};
+
+use super::{stmt::Stmt, Logic};
+
+/// An assignment of a goal's type vars which makes it hold, along with anything
+/// left over which doesn't constrain them any further.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    pub bindings: Vec<(u32, Type)>,
+    pub residual: Logic,
+}
+
+impl Display for Solution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.bindings.is_empty() {
+            write!(f, "true")?;
+        }
+        for (index, (id, ty)) in self.bindings.iter().enumerate() {
+            if index != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "${id} = {ty}")?;
+        }
+        if self.residual != Logic::True {
+            write!(f, " where {}", self.residual)?;
+        }
+        Ok(())
+    }
+}
+
+impl Logic {
+    /// Finds up to `limit` distinct solutions, searching breadth first so that
+    /// recursive impls can't starve the choices which come after them.
+    pub fn solutions(&self, state: &TypeSystem, limit: usize) -> Vec<Solution> {
+        let vars = self.vars();
+        let mut solutions = vec![];
+        let mut queue = std::collections::VecDeque::from([(state.clone(), self.clone(), 0)]);
+        while let Some((mut state, logic, depth)) = queue.pop_front() {
+            if solutions.len() >= limit {
+                break;
+            }
+            let logic = logic.reduce_to_fixpoint(&mut state, true);
+            if logic == Logic::False {
+                continue;
+            }
+            let branches = match logic.branch(&state) {
+                Some(branches) => branches,
+                None => {
+                    let solution = Solution {
+                        bindings: vars
+                            .iter()
+                            .map(|id| (*id, Type::Var(*id).resolve(&state)))
+                            .filter(|(id, ty)| ty != &Type::Var(*id))
+                            .collect(),
+                        residual: logic.resolve(&state),
+                    };
+                    if !solutions.contains(&solution) {
+                        solutions.push(solution);
+                    }
+                    continue;
+                }
+            };
+            // Each choice is a step deeper, so recursive impls are cut off like any other path.
+            if depth < state.recursion_limit {
+                queue.extend(
+                    branches
+                        .into_iter()
+                        .map(|(state, logic)| (state, logic, depth + 1)),
+                );
+            }
+        }
+        solutions
+    }
+
+    /// Splits the first choice left in the logic into one state per alternative.
+    fn branch(&self, state: &TypeSystem) -> Option<Vec<(TypeSystem, Logic)>> {
+        match self {
+            Logic::OneOf(alternatives) => Some(
+                alternatives
+                    .iter()
+                    .map(|alternative| (state.clone(), alternative.clone()))
+                    .collect(),
+            ),
+            Logic::AllOf(logics) => logics.iter().enumerate().find_map(|(index, logic)| {
+                let branches = logic.branch(state)?;
+                Some(
+                    branches
+                        .into_iter()
+                        .map(|(state, logic)| {
+                            let mut logics = logics.clone();
+                            logics[index] = logic;
+                            (state, Logic::AllOf(logics))
+                        })
+                        .collect(),
+                )
+            }),
+            Logic::Stmt(stmt) => stmt.branch(state),
+            Logic::True | Logic::False => None,
+        }
+    }
+
+    pub fn vars(&self) -> Vec<u32> {
+        let mut vars = match self {
+            Logic::OneOf(logics) | Logic::AllOf(logics) => {
+                logics.iter().flat_map(Logic::vars).collect()
+            }
+            Logic::Stmt(Stmt::Exactly { ty, is }) => [ty.vars(), is.vars()].concat(),
+            Logic::Stmt(Stmt::Extends { sub, super_ }) => [sub.vars(), super_.vars()].concat(),
+            Logic::Stmt(Stmt::HasMember { ty, member_ty, .. }) => {
+                [ty.vars(), member_ty.vars()].concat()
+            }
+            Logic::True | Logic::False => vec![],
+        };
+        vars.sort();
+        vars.dedup();
+        vars
+    }
+}
+
+impl Stmt {
+    /// Statements which are only stuck because the reducer won't guess: a free var
+    /// bound by more than one impl, or an equality which blocks on its right side.
+    fn branch(&self, state: &TypeSystem) -> Option<Vec<(TypeSystem, Logic)>> {
+        match self {
+            Stmt::Extends { sub, super_ } => {
+                let sub = sub.resolve(state);
+                let (Type::Var(_), Type::Named(named)) =
+                    (&sub, super_.resolve(state).unalias().clone())
+                else {
+                    return None;
+                };
+                let mut state = state.clone();
+                let paths = named.paths_from_any_sub_ty(&state).ok()?;
+                let super_ = Type::Named(named);
+                let candidates = sub.candidates(paths, &mut state, |ty, state| {
+                    ty.unify_variant(&super_, state)
+                });
+                Some(
+                    candidates
+                        .iter()
+                        .map(|candidate: &Candidate| {
+                            let mut state = state.clone();
+                            let logic = candidate.commit(&mut state);
+                            (state, logic)
+                        })
+                        .collect(),
+                )
+            }
+            Stmt::Exactly { ty, is } => {
+                let mut state = state.clone();
+                if ty.unify(is, &mut state) {
+                    Some(vec![(state, Logic::True)])
+                } else {
+                    Some(vec![])
+                }
+            }
+            Stmt::HasMember { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{
+        logic::Logic,
+        parser::{named, parse_test},
+        ty::Type,
+    };
+
+    const INPUT: &str = "
+        Int;
+        Bool;
+        String;
+        Display;
+        impl [] Display for Int;
+        impl [] Display for Bool;
+        impl [] Display for String;
+        ---
+        $0: Display;
+        $1 = $1;
+        ---
+        true
+    ";
+
+    /// The type `$0` is bound to in each solution. `$1` is never bound, so it
+    /// shouldn't show up.
+    fn solutions(limit: usize) -> Vec<Type> {
+        let test = parse_test(INPUT);
+        let goals = Logic::from(test.goals);
+        goals
+            .solutions(&test.ts, limit)
+            .into_iter()
+            .map(|solution| {
+                assert_eq!(solution.residual, Logic::True);
+                let [(0, ty)] = &solution.bindings[..] else {
+                    panic!("Expected one binding, found {solution}");
+                };
+                ty.clone()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_every_solution() {
+        // `Display` is bound by itself, before any impl.
+        assert_eq!(
+            solutions(10),
+            vec![
+                named("Display"),
+                named("Int"),
+                named("Bool"),
+                named("String")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_limit() {
+        assert_eq!(solutions(2), vec![named("Display"), named("Int")]);
+    }
+}
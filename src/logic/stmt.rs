@@ -1,8 +1,10 @@
 use std::fmt::Display;
 
-use crate::{state::TypeSystem, ty::Type};
+use tracing::info;
 
-use super::Logic;
+use crate::{error::TypeError, state::TypeSystem, ty::Type};
+
+use super::{explain::Trace, Logic};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Stmt {
@@ -18,7 +20,6 @@ pub enum Stmt {
         sub: Type,
         super_: Type,
     },
-    #[allow(dead_code)]
     HasMember {
         ty: Type,
         member: String,
@@ -28,10 +29,101 @@ pub enum Stmt {
 
 impl Stmt {
     pub fn reduce(&self, state: &mut TypeSystem, infer: bool) -> Logic {
+        let Some(start) = state.trace.as_mut().map(Trace::enter) else {
+            return self.reduce_untraced(state, infer);
+        };
+        let goal = self.resolve(state);
+        let res = self.reduce_untraced(state, infer);
+        let progressed = res != Logic::Stmt(self.clone());
+        if let Some(trace) = &mut state.trace {
+            trace.exit(start, goal, &res, progressed);
+        }
+        res
+    }
+
+    fn reduce_untraced(&self, state: &mut TypeSystem, infer: bool) -> Logic {
         match self {
             Stmt::Exactly { ty, is } => ty.is_exactly(is, state, infer),
-            Stmt::Extends { sub, super_ } => sub.is_bound_by(super_, state, infer),
-            Stmt::HasMember { .. } => todo!(),
+            Stmt::Extends { sub, super_ } => {
+                // Goals are compared with their aliases expanded, so a cycle is
+                // caught however its types are written.
+                let goal = self.resolve(state).expand_aliases();
+                if let Some(index) = state.stack.iter().position(|it| it == &goal) {
+                    if state.stack[index..]
+                        .iter()
+                        .all(|it| state.is_coinductive(it))
+                    {
+                        info!("Found coinductive cycle on {goal}");
+                        return Logic::True;
+                    }
+                    return Logic::Stmt(self.clone());
+                }
+                // Going any deeper would only grow the goal further, so it's given up on.
+                if state.stack.len() >= state.recursion_limit {
+                    state.error(TypeError::Overflow(Box::new(goal)));
+                    return Logic::False;
+                }
+                // The goal stays on the stack while its obligations are reduced, so
+                // any which lead back to it are caught as cycles.
+                state.stack.push(goal);
+                let res = sub.is_bound_by(super_, state, infer);
+                let res = if res == Logic::Stmt(self.clone()) {
+                    res
+                } else {
+                    res.reduce(state, infer)
+                };
+                state.stack.pop();
+                res
+            }
+            Stmt::HasMember {
+                ty,
+                member,
+                member_ty,
+            } => ty.has_member(member, member_ty, state, infer),
+        }
+    }
+
+    pub fn resolve(&self, state: &TypeSystem) -> Stmt {
+        match self {
+            Stmt::Exactly { ty, is } => Stmt::Exactly {
+                ty: ty.resolve(state),
+                is: is.resolve(state),
+            },
+            Stmt::Extends { sub, super_ } => Stmt::Extends {
+                sub: sub.resolve(state),
+                super_: super_.resolve(state),
+            },
+            Stmt::HasMember {
+                ty,
+                member,
+                member_ty,
+            } => Stmt::HasMember {
+                ty: ty.resolve(state),
+                member: member.clone(),
+                member_ty: member_ty.resolve(state),
+            },
+        }
+    }
+
+    pub fn expand_aliases(&self) -> Stmt {
+        match self {
+            Stmt::Exactly { ty, is } => Stmt::Exactly {
+                ty: ty.expand_aliases(),
+                is: is.expand_aliases(),
+            },
+            Stmt::Extends { sub, super_ } => Stmt::Extends {
+                sub: sub.expand_aliases(),
+                super_: super_.expand_aliases(),
+            },
+            Stmt::HasMember {
+                ty,
+                member,
+                member_ty,
+            } => Stmt::HasMember {
+                ty: ty.expand_aliases(),
+                member: member.clone(),
+                member_ty: member_ty.expand_aliases(),
+            },
         }
     }
 }
@@ -43,15 +135,54 @@ impl Display for Stmt {
                 write!(f, "{ty} = {is}")
             }
             Stmt::Extends { sub, super_ } => {
-                write!(f, "{super_}: {sub}")
+                write!(f, "{sub}: {super_}")
             }
             Stmt::HasMember {
                 ty,
                 member,
                 member_ty,
             } => {
-                write!(f, "{ty} has member '{member}' with type {member_ty}")
+                write!(f, "{ty} has {member}: {member_ty}")
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::TypeError,
+        logic::Logic,
+        parser::{named, parse_test},
+    };
+
+    use super::Stmt;
+
+    #[test]
+    fn test_display_extends() {
+        let stmt = Stmt::Extends {
+            sub: named("Int"),
+            super_: named("Display"),
+        };
+        assert_eq!(stmt.to_string(), "Int: Display");
+    }
+
+    #[test]
+    fn test_growing_where_clause_overflows() {
+        let input = "
+            Z;
+            Nat[T];
+            Foo;
+            impl [T] Foo for Nat[T] where Nat[Nat[T]]: Foo;
+            ---
+            Nat[Z]: Foo;
+            ---
+            false
+        ";
+        let test = parse_test(input);
+        let mut ts = test.ts;
+        let goals = Logic::from(test.goals.clone());
+        assert_eq!(goals.settle(&mut ts), Logic::False);
+        assert!(matches!(&ts.errors[..], [TypeError::Overflow(_)]));
+    }
+}
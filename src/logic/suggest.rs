@@ -0,0 +1,170 @@
+use std::fmt::Display;
+
+use crate::ty::{bound::Bound, impl_::Impl, Type};
+
+use super::{
+    explain::{Failure, Reason, Why},
+    stmt::Stmt,
+};
+
+/// A change which would make a failed goal hold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Suggestion {
+    /// Adding an impl for exactly the types in the goal.
+    Impl(Impl),
+    /// Satisfying the where-clause an existing path of impls needs.
+    Applies { path: Vec<Impl>, if_: Stmt },
+    /// Adding a where-clause for a generic.
+    WhereClause(Bound),
+}
+
+impl Failure {
+    /// The smallest changes to the impls and where-clauses which would each get
+    /// this failure past its first obstacle.
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        let mut suggestions = vec![];
+        self.suggest(&mut suggestions);
+        suggestions
+    }
+
+    fn suggest(&self, suggestions: &mut Vec<Suggestion>) {
+        match &self.reason {
+            Reason::NoPath | Reason::Mismatch => {
+                self.suggest_missing(suggestions);
+            }
+            Reason::Rejected(rejected) => {
+                let mut applies = false;
+                for rejection in rejected {
+                    let Why::WhereClause(failure) = &rejection.why else {
+                        continue;
+                    };
+                    applies = true;
+                    let suggestion = Suggestion::Applies {
+                        path: rejection.path.clone(),
+                        if_: failure.goal.clone(),
+                    };
+                    if !suggestions.contains(&suggestion) {
+                        suggestions.push(suggestion);
+                    }
+                    failure.suggest(suggestions);
+                }
+                // Only a new impl helps when nothing on the graph gets close.
+                if !applies {
+                    self.suggest_missing(suggestions);
+                }
+            }
+            Reason::Parts(parts) => parts.iter().for_each(|part| part.suggest(suggestions)),
+            Reason::Cycle => {}
+        }
+    }
+
+    /// Suggests the impl or where-clause which is missing for the goal to hold directly.
+    fn suggest_missing(&self, suggestions: &mut Vec<Suggestion>) {
+        let Stmt::Extends { sub, super_ } = &self.goal else {
+            return;
+        };
+        if !sub.vars().is_empty() || !super_.vars().is_empty() {
+            return;
+        }
+        let suggestion = match (sub.unalias(), super_.unalias()) {
+            (Type::Named(from), Type::Named(to)) => Some(Suggestion::Impl(Impl {
+                args: vec![],
+                kinds: vec![],
+                from: from.clone(),
+                to: to.clone(),
+                bounds: vec![],
+                assoc: vec![],
+            })),
+            (Type::Generic(..), Type::Named(_)) => Some(Suggestion::WhereClause(Bound {
+                sub: sub.clone(),
+                super_: super_.clone(),
+            })),
+            _ => None,
+        };
+        if let Some(suggestion) = suggestion.filter(|it| !suggestions.contains(it)) {
+            suggestions.push(suggestion);
+        }
+    }
+}
+
+impl Display for Suggestion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Suggestion::Impl(impl_) => write!(f, "add `{impl_}`"),
+            Suggestion::Applies { path, if_ } => write!(
+                f,
+                "`{}` applies if `{if_}`",
+                path.iter()
+                    .map(Impl::to_string)
+                    .collect::<Vec<_>>()
+                    .join("`, then `")
+            ),
+            Suggestion::WhereClause(bound) => write!(f, "add the where-clause `{bound}`"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        logic::{explain::Explanation, Logic},
+        parser::parse_test,
+    };
+
+    fn suggestions(input: &str) -> Vec<String> {
+        let test = parse_test(input);
+        let goals: Logic = test.goals.into();
+        let explanation = goals.explain(&test.ts);
+        assert!(matches!(explanation, Explanation::Fails(_)));
+        explanation
+            .suggestions()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_missing_impl() {
+        let suggestions = suggestions("Foo; Display; --- Foo: Display; --- false");
+        assert_eq!(suggestions, vec!["add `impl [] Display for Foo`"]);
+    }
+
+    #[test]
+    fn test_missing_where_clause() {
+        let test = parse_test("Hash; Set[T] where T: Hash; --- true; --- true");
+        // The where-clause of `Set`, as a goal with `T` still generic.
+        let bound = test.ts.decls["Set"].bounds[0].clone();
+        let explanation = Logic::from(bound).explain(&test.ts);
+        assert_eq!(
+            explanation
+                .suggestions()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["add the where-clause `T: Hash`"]
+        );
+    }
+
+    #[test]
+    fn test_conflict_suggests_nothing_more() {
+        // Both impls are rejected for binding `$0` differently to `$0 = Foo`,
+        // which no where-clause could fix.
+        let suggestions = suggestions(
+            "
+            Int;
+            Bool;
+            Foo;
+            List[T];
+            Display;
+            impl [] Display for List[Int];
+            impl [] Display for List[Bool];
+            ---
+            List[$0]: Display;
+            $0 = Foo;
+            ---
+            false
+            ",
+        );
+        assert_eq!(suggestions, vec!["add `impl [] Display for List[Foo]`"]);
+    }
+}
@@ -0,0 +1,161 @@
+use std::fmt::Display;
+
+use crate::{state::TypeSystem, ty::impl_::Impl};
+
+use super::Logic;
+
+/// A smallest set of goals which still can't hold together, with the impls
+/// they conflict through.
+#[derive(Debug, Clone)]
+pub struct UnsatCore {
+    pub goals: Vec<Logic>,
+    /// The impls taken for the goals which the conflict can't do without.
+    /// Dropping any one of them leaves the goals no longer a core, as some of
+    /// them already fail without the others.
+    pub impls: Vec<Impl>,
+}
+
+impl TypeSystem {
+    /// Shrinks contradictory goals down to a core which is still contradictory,
+    /// by dropping each goal in turn and keeping it out if the rest still fail.
+    /// The impls taken for the core are then shrunk the same way, keeping each
+    /// one out if the goals are still a core without it.
+    ///
+    /// The core is minimal, in that the rest no longer reduce to false when any
+    /// goal is dropped from it, though they may be left stuck rather than hold.
+    /// There may be smaller cores elsewhere in the goals. Nothing is returned
+    /// when the goals don't reduce to false in the first place.
+    pub fn unsat_core(&self, goals: &[Logic]) -> Option<UnsatCore> {
+        if !self.contradicts(goals) {
+            return None;
+        }
+        let mut core = goals.to_vec();
+        let mut index = 0;
+        while index < core.len() {
+            let mut rest = core.clone();
+            rest.remove(index);
+            if self.contradicts(&rest) {
+                core = rest;
+            } else {
+                index += 1;
+            }
+        }
+        let mut state = self.clone();
+        let mut impls = vec![];
+        for impl_ in Logic::from(core.clone()).impls_used(self) {
+            let mut without = state.clone();
+            without.impls.retain(|other| other != &impl_);
+            if without.is_core(&core) {
+                state = without;
+            } else {
+                impls.push(impl_);
+            }
+        }
+        Some(UnsatCore { goals: core, impls })
+    }
+
+    /// Whether the goals contradict, but none of them can be dropped and still do.
+    fn is_core(&self, goals: &[Logic]) -> bool {
+        self.contradicts(goals)
+            && (0..goals.len()).all(|index| {
+                let mut rest = goals.to_vec();
+                rest.remove(index);
+                !self.contradicts(&rest)
+            })
+    }
+
+    fn contradicts(&self, goals: &[Logic]) -> bool {
+        let mut state = self.clone();
+        for goal in goals {
+            state.add_goal(goal.clone());
+        }
+        let goal = state.goal.clone();
+        goal.settle(&mut state) == Logic::False
+    }
+}
+
+impl Display for UnsatCore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "These goals can't hold together:")?;
+        for goal in &self.goals {
+            writeln!(f, "  {goal}")?;
+        }
+        if !self.impls.is_empty() {
+            writeln!(f, "through:")?;
+            for impl_ in &self.impls {
+                writeln!(f, "  {impl_}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::parser::parse_test;
+
+    const INPUT: &str = "
+        Int;
+        Bool;
+        Str;
+        Foo;
+        Wrap[T];
+        impl [] Foo for Wrap[Int];
+        ---
+        $0 = Str;
+        Wrap[$1]: Foo;
+        Str = Str;
+        $1 = Bool;
+        ---
+        false
+    ";
+
+    #[test]
+    fn test_core() {
+        let test = parse_test(INPUT);
+        let core = test.ts.unsat_core(&test.goals).unwrap();
+        assert_eq!(
+            core.to_string(),
+            "These goals can't hold together:\n  Wrap[$1]: Foo\n  $1 = Bool\nthrough:\n  impl [] Foo for Wrap[Int]\n"
+        );
+    }
+
+    #[test]
+    fn test_impls_the_conflict_can_do_without() {
+        let test = parse_test(
+            "
+            Int;
+            Bool;
+            Foo;
+            Show;
+            Display;
+            Wrap[T];
+            impl [] Foo for Wrap[Int];
+            impl [] Display for Int;
+            impl [] Show for Int;
+            impl [] Show for Display;
+            ---
+            Wrap[$1]: Foo & Int: Show;
+            $1 = Bool;
+            ---
+            false
+            ",
+        );
+        let core = test.ts.unsat_core(&test.goals).unwrap();
+        // `Int: Show` holds by its own impl either way, so the path through
+        // `Display` isn't part of the conflict.
+        let impls = core
+            .impls
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(impls, vec!["impl [] Foo for Wrap[Int]"]);
+    }
+
+    #[test]
+    fn test_consistent_goals_have_no_core() {
+        let test = parse_test(INPUT);
+        assert!(test.ts.unsat_core(&test.goals[..3]).is_none());
+    }
+}
@@ -2,6 +2,7 @@ use clap::Parser as _;
 use cli::Command;
 
 mod cli;
+mod error;
 mod logic;
 mod parser;
 mod state;
@@ -0,0 +1,111 @@
+use chumsky::{error::Rich, input::Emitter};
+
+use crate::{state::TypeSystem, ty::Type};
+
+use super::lexer::Token;
+
+/// Reports every named type parsed since the last check which doesn't refer to a
+/// decl or alias, or gives it the wrong number of arguments.
+///
+/// This runs once everything has been parsed, so types can be used before the
+/// decl they refer to. A type is recorded each time the parser backtracks over
+/// it, so each span is only checked once.
+///
+/// A bare name given for a parameter which takes a type constructor is left to
+/// the kind check of the type it's given to, as it's meant to be missing its
+/// arguments.
+pub fn check_names<'a>(state: &mut TypeSystem, emitter: &mut Emitter<Rich<'a, Token>>) {
+    let unchecked = std::mem::take(&mut state.unchecked);
+    let constructors = unchecked
+        .iter()
+        .filter_map(|(named, span)| {
+            let decl = state.decls.get(&named.name)?;
+            Some(
+                named
+                    .args
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| decl.kind(*index) != 0)
+                    .filter_map(|(_, arg)| match arg {
+                        Type::Named(arg) if arg.args.is_empty() => Some((arg.name.clone(), *span)),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .flatten()
+        .collect::<Vec<_>>();
+    let mut checked = vec![];
+    for (named, span) in unchecked {
+        if checked.contains(&span) {
+            continue;
+        }
+        checked.push(span);
+        let constructor = named.args.is_empty()
+            && constructors.iter().any(|(name, outer)| {
+                name == &named.name
+                    && outer != &span
+                    && outer.start <= span.start
+                    && span.end <= outer.end
+            });
+        if constructor {
+            continue;
+        }
+        if let Err(error) = state.check_named(&named) {
+            emitter.emit(Rich::custom(span, error.to_string()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_errors;
+
+    #[test]
+    fn test_unknown() {
+        let errors = parse_errors("Int; impl [] Number for Int; --- Int: Number; --- true");
+        assert_eq!(errors, vec!["cannot find type `Number`"; 2]);
+    }
+
+    #[test]
+    fn test_arity() {
+        let errors = parse_errors("Int; List[T]; --- List[Int, Int]: List[Int]; --- true");
+        assert_eq!(errors, vec!["`List` takes 1 argument but 2 were given"]);
+        let errors = parse_errors("Int; Pair[A, B]; --- Pair[Int]: Pair[Int, Int]; --- true");
+        assert_eq!(errors, vec!["`Pair` takes 2 arguments but 1 was given"]);
+    }
+
+    #[test]
+    fn test_missing_args() {
+        let errors = parse_errors("Int; Display; List[T]; --- List: Display; --- true");
+        assert_eq!(errors, vec!["`List` takes 1 argument but 0 were given"]);
+    }
+
+    #[test]
+    fn test_constructor_arg() {
+        let errors =
+            parse_errors("Int; List[T]; Functor[F[_]]; --- List[Int]: Functor[List]; --- true");
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_used_before_decl() {
+        let errors = parse_errors("List[T] { next: Node[T] }; Node[T]; --- true; --- true");
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_kind() {
+        let errors = parse_errors("Int; Functor[F[_]]; --- Int: Functor[Int]; --- true");
+        assert_eq!(
+            errors,
+            vec!["expected a type constructor taking 1 argument, found `Int`"]
+        );
+    }
+
+    #[test]
+    fn test_inferred_kind() {
+        let errors = parse_errors("Int; Lift[F] { a: F[Int], b: F[Int, Int] }; --- true; --- true");
+        assert_eq!(errors, vec!["`F` takes 1 argument but 2 were given"]);
+    }
+}
@@ -0,0 +1,38 @@
+use chumsky::{
+    error::Rich,
+    extra::{self, SimpleState},
+    input::ValueInput,
+    primitive::just,
+    select,
+    span::SimpleSpan,
+    Parser,
+};
+
+use crate::{
+    parser::{lexer::Token, ty::type_parser},
+    state::TypeSystem,
+    ty::alias::Alias,
+};
+
+use super::generics::generics_parser;
+
+pub fn alias_parser<'a, I>(
+) -> impl Parser<'a, I, (), extra::Full<Rich<'a, Token>, SimpleState<TypeSystem>, ()>> + Clone
+where
+    I: ValueInput<'a, Token = Token, Span = SimpleSpan>,
+{
+    let ident = select! {
+        Token::Ident(ident) => ident,
+    };
+    just(Token::Type)
+        .ignore_then(ident)
+        .then_ignore(generics_parser().or_not())
+        .then_ignore(just(Token::Eq))
+        .then(type_parser())
+        .validate(|(name, ty), e, _| {
+            let state: &mut SimpleState<TypeSystem> = e.state();
+            let args = state.generics.clone();
+            state.clear_generics();
+            state.add_alias(Alias { name, args, ty })
+        })
+}
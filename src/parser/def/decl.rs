@@ -2,6 +2,7 @@ use chumsky::{
     error::Rich,
     extra::{self, SimpleState},
     input::ValueInput,
+    primitive::just,
     select,
     span::SimpleSpan,
     Parser,
@@ -9,7 +10,11 @@ use chumsky::{
 
 use crate::{parser::lexer::Token, state::TypeSystem, ty::decl::Decl};
 
-use super::{generics::generics_parser, where_::where_parser};
+use super::{
+    generics::decl_generics_parser,
+    members::{members_parser, Member},
+    where_::where_parser,
+};
 
 pub fn decl_parser<'a, I>(
 ) -> impl Parser<'a, I, (), extra::Full<Rich<'a, Token>, SimpleState<TypeSystem>, ()>> + Clone
@@ -19,18 +24,55 @@ where
     let ident = select! {
         Token::Ident(ident) => ident,
     };
-    ident
-        .then_ignore(generics_parser().or_not())
+    just(Token::Auto)
+        .or_not()
+        .then(ident)
+        .then(decl_generics_parser().or_not())
         .then(where_parser().or_not())
-        .validate(|(name, where_), e, _| {
-            let state: &mut SimpleState<TypeSystem> = e.state();
-            let args = state.generics.clone();
-            state.clear_generics();
-            let decl = Decl {
-                name,
-                args,
-                bounds: where_.unwrap_or_default(),
-            };
-            state.add_decl(decl)
-        })
+        .then(members_parser().or_not())
+        .validate(
+            |((((auto, name), generics), where_), members), e, emitter| {
+                let span = e.span();
+                let state: &mut SimpleState<TypeSystem> = e.state();
+                let (variance, defaults): (Vec<_>, Vec<_>) =
+                    generics.unwrap_or_default().into_iter().unzip();
+                if defaults
+                    .iter()
+                    .skip_while(|default| default.is_none())
+                    .any(Option::is_none)
+                {
+                    emitter.emit(Rich::custom(
+                        span,
+                        format!(
+                            "generics of {name} without defaults must come before those with them"
+                        ),
+                    ));
+                }
+                let args = state.generics.clone();
+                let kinds = args
+                    .iter()
+                    .map(|arg| state.kinds.get(arg).copied().unwrap_or_default())
+                    .collect();
+                state.clear_generics();
+                let (mut fields, mut assoc) = (vec![], vec![]);
+                for member in members.unwrap_or_default() {
+                    match member {
+                        Member::Field(name, ty) => fields.push((name, ty)),
+                        Member::Assoc(name) => assoc.push(name),
+                    }
+                }
+                let decl = Decl {
+                    name,
+                    auto: auto.is_some(),
+                    args,
+                    variance,
+                    kinds,
+                    defaults,
+                    bounds: where_.unwrap_or_default(),
+                    members: fields,
+                    assoc,
+                };
+                state.add_decl(decl)
+            },
+        )
 }
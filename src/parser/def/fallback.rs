@@ -0,0 +1,48 @@
+use chumsky::{
+    error::Rich,
+    extra::{self, SimpleState},
+    input::ValueInput,
+    primitive::just,
+    select,
+    span::SimpleSpan,
+    Parser,
+};
+
+use crate::{
+    parser::{lexer::Token, ty::type_parser},
+    state::TypeSystem,
+    ty::{
+        fallback::{Fallback, Target},
+        Named,
+    },
+};
+
+pub fn fallback_parser<'a, I>(
+) -> impl Parser<'a, I, (), extra::Full<Rich<'a, Token>, SimpleState<TypeSystem>, ()>> + Clone
+where
+    I: ValueInput<'a, Token = Token, Span = SimpleSpan>,
+{
+    let target = select! {
+        Token::TypeVar(var) => Target::Var(var),
+        Token::Ident(ident) => Target::Bound(ident),
+    };
+    just(Token::Default)
+        .ignore_then(target.map_with(|target, e| (target, e.span())))
+        .then_ignore(just(Token::Eq))
+        .then(type_parser())
+        .validate(|((target, span), ty), e, _| {
+            let state: &mut SimpleState<TypeSystem> = e.state();
+            let target = match target {
+                Target::Var(var) => Target::Var(state.written_var(var)),
+                Target::Bound(name) => {
+                    let named = Named {
+                        name: name.clone(),
+                        args: vec![],
+                    };
+                    state.unchecked.push((named, span));
+                    Target::Bound(name)
+                }
+            };
+            state.add_fallback(Fallback { target, ty })
+        })
+}
@@ -1,4 +1,8 @@
-use crate::{parser::lexer::Token, state::TypeSystem};
+use crate::{
+    parser::{lexer::Token, ty::type_parser},
+    state::TypeSystem,
+    ty::{variance::Variance, Type},
+};
 use chumsky::{
     error::Rich,
     extra::{self, SimpleState},
@@ -17,12 +21,63 @@ where
     let ident = select! {
         Token::Ident(ident) => ident,
     }
-    .validate(|name, extra, _| {
+    .then(kind_parser())
+    .validate(|(name, kind), extra, _| {
         let state: &mut SimpleState<TypeSystem> = extra.state();
-        state.add_generic(name);
+        state.add_generic_with_kind(name, kind);
     });
     ident
         .separated_by(just(Token::Comma))
         .collect()
         .delimited_by(just(Token::LBacket), just(Token::RBacket))
 }
+
+/// A generic of a decl, with its variance and default.
+pub type DeclGeneric = (Variance, Option<Type>);
+
+/// The generics of a decl, each of which may be marked `+` or `-` for its variance
+/// and given a default with `= Ty`.
+pub fn decl_generics_parser<'a, I>(
+) -> impl Parser<'a, I, Vec<DeclGeneric>, extra::Full<Rich<'a, Token>, SimpleState<TypeSystem>, ()>>
+       + Clone
+where
+    I: ValueInput<'a, Token = Token, Span = SimpleSpan>,
+{
+    let variance = just(Token::Plus)
+        .to(Variance::Covariant)
+        .or(just(Token::Minus).to(Variance::Contravariant))
+        .or_not()
+        .map(|variance| variance.unwrap_or(Variance::Invariant));
+    let ident = select! {
+        Token::Ident(ident) => ident,
+    };
+    // The generic is only added once its default is parsed, so defaults can
+    // only refer to the generics before them.
+    variance
+        .then(ident)
+        .then(kind_parser())
+        .then(just(Token::Eq).ignore_then(type_parser()).or_not())
+        .validate(|(((variance, name), kind), default), extra, _| {
+            let state: &mut SimpleState<TypeSystem> = extra.state();
+            state.add_generic_with_kind(name, kind);
+            (variance, default)
+        })
+        .separated_by(just(Token::Comma))
+        .collect()
+        .delimited_by(just(Token::LBacket), just(Token::RBacket))
+}
+
+/// The holes after a type constructor's name, like `[_, _]`, giving how many
+/// arguments it takes.
+fn kind_parser<'a, I>(
+) -> impl Parser<'a, I, Option<usize>, extra::Full<Rich<'a, Token>, SimpleState<TypeSystem>, ()>> + Clone
+where
+    I: ValueInput<'a, Token = Token, Span = SimpleSpan>,
+{
+    just(Token::Underscore)
+        .separated_by(just(Token::Comma))
+        .at_least(1)
+        .count()
+        .delimited_by(just(Token::LBacket), just(Token::RBacket))
+        .or_not()
+}
@@ -13,10 +13,10 @@ use crate::{
         ty::{named::named_parser, type_parser},
     },
     state::TypeSystem,
-    ty::impl_::Impl,
+    ty::{impl_::Impl, Named, Type},
 };
 
-use super::{generics::generics_parser, where_::where_parser};
+use super::{generics::generics_parser, members::assoc_parser, where_::where_parser};
 
 pub fn impl_parser<'a, I>(
 ) -> impl Parser<'a, I, (), extra::Full<Rich<'a, Token>, SimpleState<TypeSystem>, ()>> + Clone
@@ -25,20 +25,149 @@ where
 {
     just(Token::Impl)
         .then(generics_parser())
-        .ignore_then(named_parser(type_parser()))
+        .ignore_then(head_parser())
         .then_ignore(just(Token::For))
-        .then(named_parser(type_parser()))
+        .then(head_parser())
         .then(where_parser().or_not())
-        .validate(|((to, from), where_), e, _| {
-            let state: &mut SimpleState<TypeSystem> = e.state();
-            let args = state.generics.clone();
-            state.clear_generics();
-            let impl_ = Impl {
-                args,
-                from,
-                to,
-                bounds: where_.unwrap_or_default(),
-            };
-            state.add_impl(impl_);
-        })
+        .then(assoc_parser().or_not())
+        .validate(
+            |((((to, to_span), (from, from_span)), where_), assoc), e, emitter| {
+                let span = e.span();
+                let state: &mut SimpleState<TypeSystem> = e.state();
+                let args = state.generics.clone();
+                let kinds = args
+                    .iter()
+                    .map(|arg| state.kinds.get(arg).copied().unwrap_or_default())
+                    .collect();
+                state.clear_generics();
+                state.unchecked.push((to.clone(), to_span));
+                state.unchecked.push((from.clone(), from_span));
+                // An impl for an alias is an impl for whatever it names.
+                let from = match state.aliases.get(&from.name) {
+                    Some(alias) => match alias.expand(&from.args) {
+                        Ok(Type::Named(named)) => named,
+                        Ok(_) => {
+                            emitter.emit(Rich::custom(
+                                from_span,
+                                format!("alias {} doesn't name a decl", from),
+                            ));
+                            from
+                        }
+                        Err(_) => from,
+                    },
+                    None => from,
+                };
+                let [from, to] = [from, to].map(|named| match state.decls.get(&named.name) {
+                    Some(decl) => decl.apply_defaults(&named).unwrap_or(named),
+                    None => named,
+                });
+                let impl_ = Impl {
+                    args,
+                    kinds,
+                    from,
+                    to,
+                    bounds: where_.unwrap_or_default(),
+                    assoc: assoc.unwrap_or_default(),
+                };
+                if let Err(error) = state.add_impl(impl_) {
+                    emitter.emit(Rich::custom(span, error.to_string()));
+                }
+            },
+        )
+}
+
+/// A named type in the head of an impl, along with where it was written.
+fn head_parser<'a, I>(
+) -> impl Parser<'a, I, (Named, SimpleSpan), extra::Full<Rich<'a, Token>, SimpleState<TypeSystem>, ()>>
+       + Clone
+where
+    I: ValueInput<'a, Token = Token, Span = SimpleSpan>,
+{
+    named_parser(type_parser()).map_with(|named, e| (named, e.span()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_errors;
+
+    #[test]
+    fn test_where_clause_in_scope() {
+        let input = "
+            Hash;
+            Display;
+            Set[T] where T: Hash;
+            impl [T] Display for Set[T] where T: Hash;
+            ---
+            ---
+            true
+        ";
+        assert!(parse_errors(input).is_empty());
+    }
+
+    #[test]
+    fn test_unsatisfied_where_clause() {
+        let input = "
+            Hash;
+            Display;
+            Set[T] where T: Hash;
+            impl [T] Display for Set[T];
+            ---
+            ---
+            true
+        ";
+        let errors = parse_errors(input);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("doesn't satisfy `T: Hash`"));
+    }
+
+    #[test]
+    fn test_missing_assoc() {
+        let input = "
+            List[T];
+            Iterator { type Item };
+            impl [T] Iterator for List[T];
+            ---
+            ---
+            true
+        ";
+        let errors = parse_errors(input);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("doesn't bind the associated type `Item`"));
+    }
+
+    #[test]
+    fn test_unknown_assoc() {
+        let input = "
+            List[T];
+            Iterator { type Item };
+            impl [T] Iterator for List[T] { type Item = T, type Key = T };
+            ---
+            ---
+            true
+        ";
+        let errors = parse_errors(input);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("`Iterator` has no associated type `Key`"));
+    }
+
+    #[test]
+    fn test_kind_of_generic() {
+        let input = "
+            Show;
+            Wrap[F[_]];
+            List[T];
+            impl [T] Show for Wrap[T];
+            impl [F[_]] Show for List[F];
+            ---
+            ---
+            true
+        ";
+        assert_eq!(
+            parse_errors(input),
+            vec![
+                "expected a type constructor taking 1 argument, found `T`",
+                "`F` takes 1 argument but 0 were given",
+            ]
+        );
+    }
 }
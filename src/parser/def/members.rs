@@ -0,0 +1,121 @@
+use chumsky::{
+    error::Rich,
+    extra::{self, SimpleState},
+    input::ValueInput,
+    primitive::just,
+    select,
+    span::SimpleSpan,
+    IterParser as _, Parser,
+};
+
+use crate::{
+    parser::{lexer::Token, ty::type_parser},
+    state::TypeSystem,
+    ty::Type,
+};
+
+/// An item in the body of a decl.
+pub enum Member {
+    Field(String, Type),
+    Assoc(String),
+}
+
+pub fn members_parser<'a, I>(
+) -> impl Parser<'a, I, Vec<Member>, extra::Full<Rich<'a, Token>, SimpleState<TypeSystem>, ()>> + Clone
+where
+    I: ValueInput<'a, Token = Token, Span = SimpleSpan>,
+{
+    let ident = select! {
+        Token::Ident(ident) => ident,
+    };
+    let field = ident
+        .then_ignore(just(Token::Colon))
+        .then(type_parser())
+        .map(|(name, ty)| Member::Field(name, ty));
+    let assoc = just(Token::Type).ignore_then(ident).map(Member::Assoc);
+    field
+        .or(assoc)
+        .separated_by(just(Token::Comma))
+        .allow_trailing()
+        .collect()
+        .delimited_by(just(Token::LBrace), just(Token::RBrace))
+        .labelled("members")
+}
+
+/// The body of an impl, binding the associated types of the decl being implemented.
+pub fn assoc_parser<'a, I>(
+) -> impl Parser<'a, I, Vec<(String, Type)>, extra::Full<Rich<'a, Token>, SimpleState<TypeSystem>, ()>>
+       + Clone
+where
+    I: ValueInput<'a, Token = Token, Span = SimpleSpan>,
+{
+    let ident = select! {
+        Token::Ident(ident) => ident,
+    };
+    just(Token::Type)
+        .ignore_then(ident)
+        .then_ignore(just(Token::Eq))
+        .then(type_parser())
+        .separated_by(just(Token::Comma))
+        .allow_trailing()
+        .collect()
+        .delimited_by(just(Token::LBrace), just(Token::RBrace))
+        .labelled("associated types")
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{extra::SimpleState, Parser};
+
+    use crate::{parser::lexer::lex, state::TypeSystem};
+
+    use super::Member;
+
+    #[test]
+    fn test_empty() {
+        let input = lex("{}");
+        let mut state = SimpleState::from(TypeSystem::new());
+        let result = super::members_parser()
+            .parse_with_state(input, &mut state)
+            .unwrap();
+
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_many() {
+        let input = lex("{ len: Int, get: T }");
+        let mut state = SimpleState::from(TypeSystem::new());
+        let result = super::members_parser()
+            .parse_with_state(input, &mut state)
+            .unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert!(matches!(&result[0], Member::Field(name, _) if name == "len"));
+        assert!(matches!(&result[1], Member::Field(name, _) if name == "get"));
+    }
+
+    #[test]
+    fn test_assoc() {
+        let input = lex("{ type Item, next: Int }");
+        let mut state = SimpleState::from(TypeSystem::new());
+        let result = super::members_parser()
+            .parse_with_state(input, &mut state)
+            .unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert!(matches!(&result[0], Member::Assoc(name) if name == "Item"));
+    }
+
+    #[test]
+    fn test_assoc_bindings() {
+        let input = lex("{ type Item = Int, type Key = String }");
+        let mut state = SimpleState::from(TypeSystem::new());
+        let result = super::assoc_parser()
+            .parse_with_state(input, &mut state)
+            .unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[1].0, "Key");
+    }
+}
@@ -1,4 +1,7 @@
+pub mod alias;
 pub mod decl;
+pub mod fallback;
 pub mod generics;
 pub mod impl_;
+pub mod members;
 pub mod where_;
@@ -11,16 +11,40 @@ use logos::Logos;
 pub enum Token {
     #[token(":")]
     Colon,
+    #[token("::")]
+    DoubleColon,
+    #[token("<")]
+    Lt,
+    #[token(">")]
+    Gt,
+    #[token("as")]
+    As,
+    #[token("default")]
+    Default,
+    #[token("type")]
+    Type,
     #[token("=")]
     Eq,
+    #[token("->")]
+    Arrow,
     #[token("goal")]
     Goal,
     #[token("eval")]
     Eval,
     #[token("resolve")]
     Resolve,
+    #[token("solve")]
+    Solve,
+    #[token("explain")]
+    Explain,
+    #[token("join")]
+    Join,
+    #[token("meet")]
+    Meet,
     #[token("decl")]
     Decl,
+    #[token("auto")]
+    Auto,
     #[token("list")]
     List,
     #[token(",")]
@@ -33,11 +57,19 @@ pub enum Token {
     LParen,
     #[token(")")]
     RParen,
+    #[token("{")]
+    LBrace,
+    #[token("}")]
+    RBrace,
     #[token("_")]
     Underscore,
     #[token(";")]
     Semi,
-    #[regex("-+")]
+    #[token("+")]
+    Plus,
+    #[token("-")]
+    Minus,
+    #[regex("--+")]
     Sep,
     #[token("vars")]
     Vars,
@@ -49,10 +81,16 @@ pub enum Token {
     New,
     #[token("for")]
     For,
+    #[token("has")]
+    Has,
     #[token("&")]
     And,
     #[token("|")]
     Or,
+    #[token("Any")]
+    Any,
+    #[token("Never")]
+    Never,
     #[token("true")]
     True,
     #[token("false")]
@@ -84,31 +122,50 @@ impl Display for Token {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Token::Colon => write!(f, ":"),
+            Token::DoubleColon => write!(f, "::"),
+            Token::Lt => write!(f, "<"),
+            Token::Gt => write!(f, ">"),
+            Token::As => write!(f, "as"),
+            Token::Default => write!(f, "default"),
+            Token::Type => write!(f, "type"),
             Token::Eq => write!(f, "="),
+            Token::Arrow => write!(f, "->"),
             Token::Comma => write!(f, ","),
             Token::LBacket => write!(f, "["),
             Token::RBacket => write!(f, "]"),
             Token::LParen => write!(f, "("),
             Token::RParen => write!(f, ")"),
+            Token::LBrace => write!(f, "{{"),
+            Token::RBrace => write!(f, "}}"),
             Token::And => write!(f, "&"),
             Token::Or => write!(f, "|"),
             Token::Underscore => write!(f, "_"),
             Token::Semi => write!(f, ";"),
+            Token::Plus => write!(f, "+"),
+            Token::Minus => write!(f, "-"),
             Token::Sep => write!(f, "-"),
             Token::Impl => write!(f, "impl"),
             Token::New => write!(f, "new"),
             Token::For => write!(f, "for"),
+            Token::Has => write!(f, "has"),
             Token::List => write!(f, "list"),
             Token::Ident(ident) => write!(f, "{}", ident),
             Token::Goal => write!(f, "goal"),
             Token::Vars => write!(f, "vars"),
             Token::Eval => write!(f, "eval"),
             Token::Decl => write!(f, "decl"),
+            Token::Auto => write!(f, "auto"),
+            Token::Any => write!(f, "Any"),
+            Token::Never => write!(f, "Never"),
             Token::True => write!(f, "true"),
             Token::False => write!(f, "true"),
             Token::Error => write!(f, "error"),
             Token::Where => write!(f, "where"),
             Token::Resolve => write!(f, "resolve"),
+            Token::Solve => write!(f, "solve"),
+            Token::Explain => write!(f, "explain"),
+            Token::Join => write!(f, "join"),
+            Token::Meet => write!(f, "meet"),
             Token::TypeVar(var) => write!(f, "${}", var),
         }
     }
@@ -8,7 +8,7 @@ use chumsky::{
 };
 
 use crate::{
-    parser::{lexer::Token, ty::type_parser},
+    parser::{lexer::Token, ty::type_atom_parser},
     state::TypeSystem,
     ty::bound::Bound,
 };
@@ -18,8 +18,8 @@ pub fn bound_parser<'a, I>(
 where
     I: ValueInput<'a, Token = Token, Span = SimpleSpan>,
 {
-    type_parser()
+    type_atom_parser()
         .then_ignore(just(Token::Colon))
-        .then(type_parser())
+        .then(type_atom_parser())
         .map(|(sub, super_)| Bound { sub, super_ })
 }
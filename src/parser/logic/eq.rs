@@ -9,7 +9,7 @@ use chumsky::{
 
 use crate::{
     logic::stmt::Stmt,
-    parser::{lexer::Token, ty::type_parser},
+    parser::{lexer::Token, ty::type_atom_parser},
     state::TypeSystem,
 };
 
@@ -18,8 +18,8 @@ pub fn eq_parser<'a, I>(
 where
     I: ValueInput<'a, Token = Token, Span = SimpleSpan>,
 {
-    type_parser()
+    type_atom_parser()
         .then_ignore(just(Token::Eq))
-        .then(type_parser())
+        .then(type_atom_parser())
         .map(|(ty, is)| Stmt::Exactly { ty, is })
 }
@@ -0,0 +1,35 @@
+use chumsky::{
+    error::Rich,
+    extra::{self, SimpleState},
+    input::ValueInput,
+    primitive::just,
+    select,
+    span::SimpleSpan,
+    Parser,
+};
+
+use crate::{
+    logic::stmt::Stmt,
+    parser::{lexer::Token, ty::type_atom_parser},
+    state::TypeSystem,
+};
+
+pub fn member_parser<'a, I>(
+) -> impl Parser<'a, I, Stmt, extra::Full<Rich<'a, Token>, SimpleState<TypeSystem>, ()>> + Clone
+where
+    I: ValueInput<'a, Token = Token, Span = SimpleSpan>,
+{
+    let ident = select! {
+        Token::Ident(ident) => ident,
+    };
+    type_atom_parser()
+        .then_ignore(just(Token::Has))
+        .then(ident)
+        .then_ignore(just(Token::Colon))
+        .then(type_atom_parser())
+        .map(|((ty, member), member_ty)| Stmt::HasMember {
+            ty,
+            member,
+            member_ty,
+        })
+}
@@ -10,6 +10,7 @@ use chumsky::{
     Parser,
 };
 use eq::eq_parser;
+use member::member_parser;
 
 use crate::{logic::Logic, state::TypeSystem};
 
@@ -17,6 +18,7 @@ use super::lexer::Token;
 
 pub mod bound;
 pub mod eq;
+pub mod member;
 
 pub fn logic_parser<'a, I>(
 ) -> impl Parser<'a, I, Logic, extra::Full<Rich<'a, Token>, SimpleState<TypeSystem>, ()>> + Clone
@@ -31,6 +33,7 @@ where
         let atom = choice((
             logic.delimited_by(just(Token::LParen), just(Token::RParen)),
             eq_parser().map(Logic::Stmt),
+            member_parser().map(Logic::Stmt),
             bound_parser().map(Logic::from),
             literal,
         ));
@@ -138,6 +141,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_has_member() {
+        let input = lex("A has len : B");
+        let mut state = SimpleState::from(TypeSystem::default());
+        let output = logic_parser().parse_with_state(input, &mut state).unwrap();
+        if let Logic::Stmt(Stmt::HasMember {
+            ty,
+            member,
+            member_ty,
+        }) = output
+        {
+            assert_eq!(
+                ty,
+                Type::Named(Named {
+                    name: "A".to_string(),
+                    args: vec![],
+                })
+            );
+            assert_eq!(member, "len");
+            assert_eq!(
+                member_ty,
+                Type::Named(Named {
+                    name: "B".to_string(),
+                    args: vec![],
+                })
+            );
+        } else {
+            panic!("Expected HasMember");
+        }
+    }
+
+    #[test]
+    fn test_written_vars() {
+        let input = lex("$1 = $0 & $0 = $1");
+        let mut state = SimpleState::from(TypeSystem::default());
+        // A var made before the goals, as freshening an impl would.
+        state.new_type_var();
+        let output = logic_parser().parse_with_state(input, &mut state).unwrap();
+        assert_eq!(
+            output,
+            Logic::AllOf(vec![
+                Logic::Stmt(Stmt::Exactly {
+                    ty: Type::Var(1),
+                    is: Type::Var(2),
+                }),
+                Logic::Stmt(Stmt::Exactly {
+                    ty: Type::Var(2),
+                    is: Type::Var(1),
+                }),
+            ])
+        );
+    }
+
     #[test]
     fn test_all_of() {
         let input = lex("A = B & C = D & E = F");
@@ -205,4 +261,23 @@ mod tests {
             panic!("Expected AllOf");
         }
     }
+
+    #[test]
+    fn test_union_type() {
+        let input = lex("(A | B): C | D: E");
+        let mut state = SimpleState::from(TypeSystem::default());
+        let output = logic_parser().parse_with_state(input, &mut state).unwrap();
+        if let Logic::OneOf(logics) = output {
+            assert_eq!(logics.len(), 2);
+            assert!(matches!(
+                &logics[0],
+                Logic::Stmt(Stmt::Extends {
+                    sub: Type::Union(_),
+                    ..
+                })
+            ));
+        } else {
+            panic!("Expected OneOf");
+        }
+    }
 }
@@ -1,9 +1,11 @@
+pub mod check;
 pub mod def;
 pub mod lexer;
 pub mod logic;
 pub mod repl;
 pub mod ty;
 
+use check::check_names;
 use chumsky::{
     error::Rich,
     extra::{self, SimpleState},
@@ -12,9 +14,9 @@ use chumsky::{
     span::SimpleSpan,
     IterParser, Parser,
 };
-use def::{decl::decl_parser, impl_::impl_parser};
+use def::{alias::alias_parser, decl::decl_parser, fallback::fallback_parser, impl_::impl_parser};
 use lexer::Token;
-use logic::{bound::bound_parser, logic_parser};
+use logic::logic_parser;
 
 use crate::{logic::Logic, state::TypeSystem};
 
@@ -33,12 +35,13 @@ where
     let impl_ = impl_parser();
     let decl = decl_parser();
     let env = impl_
+        .or(alias_parser())
+        .or(fallback_parser())
         .or(decl)
         .separated_by(just(Token::Semi))
         .allow_trailing()
         .labelled("env");
-    let goals = bound_parser()
-        .map(Logic::from)
+    let goals = logic_parser()
         .separated_by(just(Token::Semi))
         .allow_trailing()
         .collect()
@@ -49,8 +52,9 @@ where
     env.then(just(Token::Sep))
         .ignore_then(goals)
         .then(expected)
-        .map_with(|(goals, expected), e| {
+        .validate(|(goals, expected), e, emitter| {
             let state: &mut SimpleState<TypeSystem> = e.state();
+            check_names(state, emitter);
             Test {
                 ts: state.clone(),
                 goals,
@@ -58,3 +62,31 @@ where
             }
         })
 }
+
+/// Parses a test written like a `.type` file, panicking on any error.
+#[cfg(test)]
+pub fn parse_test(input: &str) -> Test {
+    test_parser()
+        .parse_with_state(lexer::lex(input), &mut SimpleState::from(TypeSystem::new()))
+        .unwrap()
+}
+
+/// The errors parsing a test written like a `.type` file reports, as printed.
+#[cfg(test)]
+pub fn parse_errors(input: &str) -> Vec<String> {
+    test_parser()
+        .parse_with_state(lexer::lex(input), &mut SimpleState::from(TypeSystem::new()))
+        .into_errors()
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// A named type without args.
+#[cfg(test)]
+pub fn named(name: &str) -> crate::ty::Type {
+    crate::ty::Type::Named(crate::ty::Named {
+        name: name.to_string(),
+        args: vec![],
+    })
+}
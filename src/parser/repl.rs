@@ -11,7 +11,8 @@ use chumsky::{
 use crate::{logic::Logic, state::TypeSystem, ty::Type};
 
 use super::{
-    def::{decl::decl_parser, impl_::impl_parser},
+    check::check_names,
+    def::{alias::alias_parser, decl::decl_parser, impl_::impl_parser},
     lexer::Token,
     logic::logic_parser,
     ty::type_parser,
@@ -22,9 +23,14 @@ pub enum ReplCommand {
     Eval(Logic),
     Impl,
     Decl,
+    Alias,
     List(ListItem),
     New(Type),
     Resolve(Type),
+    Solve(Logic),
+    Explain(Logic),
+    Join(Type, Type),
+    Meet(Type, Type),
 }
 
 pub enum ListItem {
@@ -48,6 +54,7 @@ where
     let decl = just(Token::Decl)
         .ignore_then(decl_parser())
         .map(|_| ReplCommand::Decl);
+    let alias = alias_parser().map(|_| ReplCommand::Alias);
     let list = just(Token::List)
         .ignore_then(list_item_parser())
         .map(ReplCommand::List);
@@ -57,7 +64,30 @@ where
     let resolve = just(Token::Resolve)
         .ignore_then(type_parser())
         .map(ReplCommand::Resolve);
-    choice((goal, eval, decl, list, new, impl_, resolve))
+    let solve = just(Token::Solve)
+        .ignore_then(logic_parser())
+        .map(ReplCommand::Solve);
+    let explain = just(Token::Explain)
+        .ignore_then(logic_parser())
+        .map(ReplCommand::Explain);
+    let join = just(Token::Join)
+        .ignore_then(type_parser())
+        .then_ignore(just(Token::Comma))
+        .then(type_parser())
+        .map(|(first, second)| ReplCommand::Join(first, second));
+    let meet = just(Token::Meet)
+        .ignore_then(type_parser())
+        .then_ignore(just(Token::Comma))
+        .then(type_parser())
+        .map(|(first, second)| ReplCommand::Meet(first, second));
+    choice((
+        goal, eval, decl, alias, list, new, impl_, resolve, solve, explain, join, meet,
+    ))
+    .validate(|command, e, emitter| {
+        let state: &mut SimpleState<TypeSystem> = e.state();
+        check_names(state, emitter);
+        command
+    })
 }
 
 fn list_item_parser<'a, I>(
@@ -6,18 +6,61 @@ use chumsky::{
     recursive::recursive,
     select,
     span::SimpleSpan,
-    Parser,
+    IterParser, Parser,
 };
 use named::named_parser;
 
-use crate::{state::TypeSystem, ty::Type};
+use crate::{error::TypeError, state::TypeSystem, ty::Type};
 
 use super::lexer::Token;
 
 pub mod named;
 
+/// A type, where unions and intersections may appear without parentheses.
 pub fn type_parser<'a, I>(
 ) -> impl Parser<'a, I, Type, extra::Full<Rich<'a, Token>, SimpleState<TypeSystem>, ()>> + Clone
+where
+    I: ValueInput<'a, Token = Token, Span = SimpleSpan>,
+{
+    recursive(|ty| {
+        let intersection = type_atom(ty)
+            .separated_by(just(Token::And))
+            .at_least(1)
+            .collect::<Vec<_>>()
+            .map(|mut tys| {
+                if tys.len() == 1 {
+                    tys.pop().unwrap()
+                } else {
+                    Type::Intersection(tys)
+                }
+            });
+        intersection
+            .separated_by(just(Token::Or))
+            .at_least(1)
+            .collect::<Vec<_>>()
+            .map(|mut tys| {
+                if tys.len() == 1 {
+                    tys.pop().unwrap()
+                } else {
+                    Type::Union(tys)
+                }
+            })
+    })
+}
+
+/// A type which stops before any `|` or `&` outside of brackets, for where those
+/// are logic operators instead.
+pub fn type_atom_parser<'a, I>(
+) -> impl Parser<'a, I, Type, extra::Full<Rich<'a, Token>, SimpleState<TypeSystem>, ()>> + Clone
+where
+    I: ValueInput<'a, Token = Token, Span = SimpleSpan>,
+{
+    type_atom(type_parser())
+}
+
+fn type_atom<'a, I>(
+    ty: impl Parser<'a, I, Type, extra::Full<Rich<'a, Token>, SimpleState<TypeSystem>, ()>> + Clone + 'a,
+) -> impl Parser<'a, I, Type, extra::Full<Rich<'a, Token>, SimpleState<TypeSystem>, ()>> + Clone
 where
     I: ValueInput<'a, Token = Token, Span = SimpleSpan>,
 {
@@ -25,20 +68,99 @@ where
         let state: &mut SimpleState<TypeSystem> = e.state();
         Type::Var(state.new_type_var())
     });
+    let ident = select! {
+        Token::Ident(ident) => ident,
+    };
 
-    recursive(|ty| {
+    recursive(|atom| {
+        let projection = ty
+            .clone()
+            .then_ignore(just(Token::As))
+            .then(named_parser(ty.clone()))
+            .delimited_by(just(Token::Lt), just(Token::Gt))
+            .then_ignore(just(Token::DoubleColon))
+            .then(ident)
+            .map(|((ty, decl), name)| Type::Projection {
+                ty: Box::new(ty),
+                decl,
+                name,
+            });
+        let function = ty
+            .clone()
+            .separated_by(just(Token::Comma))
+            .collect()
+            .delimited_by(just(Token::LParen), just(Token::RParen))
+            .then_ignore(just(Token::Arrow))
+            .then(atom)
+            .map(|(params, ret)| Type::Function {
+                params,
+                ret: Box::new(ret),
+            });
+        // A single type in parentheses is just grouped, unless it has a trailing comma.
+        let tuple = ty
+            .clone()
+            .separated_by(just(Token::Comma))
+            .collect::<Vec<_>>()
+            .then(just(Token::Comma).or_not())
+            .delimited_by(just(Token::LParen), just(Token::RParen))
+            .map(|(mut tys, trailing)| {
+                if tys.len() == 1 && trailing.is_none() {
+                    tys.pop().unwrap()
+                } else {
+                    Type::Tuple(tys)
+                }
+            });
+        let builtin = select! {
+            Token::Any => Type::Any,
+            Token::Never => Type::Never,
+        };
         type_var_parser()
             .or(wildcard)
-            .or(named_parser(ty).map_with(|named, extra| {
-                let state: &mut SimpleState<TypeSystem> = extra.state();
+            .or(builtin)
+            .or(projection)
+            .or(function)
+            .or(tuple)
+            .or(named_parser(ty).validate(|named, e, emitter| {
+                let span = e.span();
+                let state: &mut SimpleState<TypeSystem> = e.state();
                 if state.generics.contains(&named.name) {
-                    Type::Generic(named.name)
-                } else {
-                    Type::Named(named)
+                    if !named.args.is_empty() {
+                        // The kind of a generic is inferred from where it's first applied.
+                        let kind = *state
+                            .kinds
+                            .entry(named.name.clone())
+                            .or_insert(named.args.len());
+                        if kind != named.args.len() {
+                            let error = TypeError::Arity {
+                                name: named.name.clone(),
+                                min: kind,
+                                max: kind,
+                                found: named.args.len(),
+                            };
+                            emitter.emit(Rich::custom(span, error.to_string()));
+                        }
+                    }
+                    return Type::Generic(named.name, named.args);
+                }
+                state.unchecked.push((named.clone(), span));
+                if let Some(alias) = state.aliases.get(&named.name) {
+                    return match alias.expand(&named.args) {
+                        Ok(ty) => Type::Alias {
+                            alias: named,
+                            ty: Box::new(ty),
+                        },
+                        // Reported once the named type is checked.
+                        Err(_) => Type::Named(named),
+                    };
+                }
+                match state.decls.get(&named.name) {
+                    Some(decl) => Type::Named(decl.apply_defaults(&named).unwrap_or(named)),
+                    None => Type::Named(named),
                 }
             }))
     })
 }
+
 pub fn type_var_parser<'a, I>(
 ) -> impl Parser<'a, I, Type, extra::Full<Rich<'a, Token>, SimpleState<TypeSystem>, ()>> + Clone
 where
@@ -47,18 +169,8 @@ where
     select! {
         Token::TypeVar(var) => var,
     }
-    .validate(|var, e, emitter| {
-        let span = e.span();
+    .map_with(|var, e| {
         let state: &mut SimpleState<TypeSystem> = e.state();
-        if state.type_vars.contains_key(&var) {
-            Type::Var(var)
-        } else {
-            emitter.emit(Rich::custom(
-                span,
-                format!("unknown type variable ${var}, creating a new one.",),
-            ));
-            let var = state.new_type_var();
-            Type::Var(var)
-        }
+        Type::Var(state.written_var(var))
     })
 }
@@ -1,21 +1,48 @@
 use std::collections::HashMap;
 
+use chumsky::span::SimpleSpan;
 use tracing::info;
 
 use crate::{
-    logic::Logic,
-    ty::{args::GeneircArgs, bound::Bound, decl::Decl, impl_::Impl, Type},
+    error::TypeError,
+    logic::{explain::Trace, stmt::Stmt, Logic},
+    ty::{
+        alias::Alias, args::GeneircArgs, bound::Bound, decl::Decl, fallback::Fallback, impl_::Impl,
+        Named, Type,
+    },
 };
 
 #[derive(Debug, Clone)]
 pub struct TypeSystem {
     pub counter: u32,
     pub decls: HashMap<String, Decl>,
+    pub aliases: HashMap<String, Alias>,
+    /// Types to fall back on for type vars which are still free at a fixpoint.
+    pub fallbacks: Vec<Fallback>,
+    /// Where-clauses in scope, which are assumed to hold for the types they mention.
     pub bounds: Vec<Bound>,
     pub impls: Vec<Impl>,
     pub type_vars: HashMap<u32, Type>,
+    /// The type var each `$N` written in the input stands for.
+    pub written_vars: HashMap<u32, u32>,
     pub generics: GeneircArgs,
+    /// How many arguments each generic in scope takes, for those which are known
+    /// to be type constructors.
+    pub kinds: HashMap<String, usize>,
     pub goal: Logic,
+    /// How deep a path of impls can go before giving up.
+    pub recursion_limit: usize,
+    pub errors: Vec<TypeError>,
+    /// Whether `add_impl` rejects impls which overlap with an existing one.
+    pub deny_overlap: bool,
+    /// Whether the most specific impls are preferred when several apply.
+    pub specialize: bool,
+    /// The goals currently being proven, innermost last.
+    pub stack: Vec<Stmt>,
+    /// Named types parsed since they were last checked, with where they were written.
+    pub unchecked: Vec<(Named, SimpleSpan)>,
+    /// What the reducer has done, kept only while explaining a goal.
+    pub trace: Option<Trace>,
 }
 
 impl TypeSystem {
@@ -23,11 +50,22 @@ impl TypeSystem {
         Self {
             counter: 0,
             decls: HashMap::new(),
+            aliases: HashMap::new(),
+            fallbacks: Vec::new(),
             impls: Vec::new(),
             type_vars: HashMap::new(),
+            written_vars: HashMap::new(),
             bounds: Vec::new(),
             generics: GeneircArgs::default(),
+            kinds: HashMap::new(),
             goal: Logic::True,
+            recursion_limit: 64,
+            errors: Vec::new(),
+            deny_overlap: false,
+            specialize: false,
+            stack: Vec::new(),
+            unchecked: Vec::new(),
+            trace: None,
         }
     }
 
@@ -36,18 +74,92 @@ impl TypeSystem {
         self.decls.insert(decl.name.to_string(), decl);
     }
 
+    pub fn add_alias(&mut self, alias: Alias) {
+        info!("Adding alias: {}", alias.name);
+        self.aliases.insert(alias.name.to_string(), alias);
+    }
+
+    pub fn add_fallback(&mut self, fallback: Fallback) {
+        info!("Adding fallback: {}", fallback);
+        self.fallbacks.push(fallback);
+    }
+
+    /// Checks that a named type refers to a decl or alias and gives it the right
+    /// number of arguments.
+    pub fn check_named(&self, named: &Named) -> Result<(), TypeError> {
+        if let Some(decl) = self.decls.get(&named.name) {
+            decl.check_arity(named)?;
+            for (index, arg) in named.args.iter().enumerate() {
+                self.check_kind(arg, decl.kind(index))?;
+            }
+            Ok(())
+        } else if let Some(alias) = self.aliases.get(&named.name) {
+            alias.expand(&named.args).map(|_| ())
+        } else {
+            Err(TypeError::Unknown(named.name.clone()))
+        }
+    }
+
+    /// Checks that a type given for a parameter taking `kind` arguments is a
+    /// constructor taking that many, or is a proper type when `kind` is zero.
+    fn check_kind(&self, ty: &Type, kind: usize) -> Result<(), TypeError> {
+        if kind == 0 {
+            return Ok(());
+        }
+        match ty {
+            Type::Named(named)
+                if named.args.is_empty()
+                    && self
+                        .decls
+                        .get(&named.name)
+                        .is_some_and(|decl| decl.args.len() == kind) =>
+            {
+                Ok(())
+            }
+            Type::Generic(_, args) if args.is_empty() => Ok(()),
+            Type::Var(_) => Ok(()),
+            _ => Err(TypeError::Kind {
+                ty: ty.clone(),
+                kind,
+            }),
+        }
+    }
+
     pub fn add_generic(&mut self, name: String) {
         info!("Adding generic: {}", name);
         self.generics.push(name);
     }
 
+    /// Adds a generic, which is a type constructor when it's given a kind.
+    pub fn add_generic_with_kind(&mut self, name: String, kind: Option<usize>) {
+        if let Some(kind) = kind {
+            self.kinds.insert(name.clone(), kind);
+        }
+        self.add_generic(name);
+    }
+
     pub fn clear_generics(&mut self) {
         self.generics = GeneircArgs::default();
+        self.kinds.clear();
     }
 
-    pub fn add_impl(&mut self, impl_: Impl) {
+    /// Adds an impl, unless overlapping impls are denied and it conflicts with an existing one.
+    pub fn add_impl(&mut self, impl_: Impl) -> Result<(), TypeError> {
         info!("Adding impl: {}", impl_);
+        impl_.check(self)?;
+        if self.deny_overlap {
+            for existing in self.impls.clone() {
+                if let Some(ty) = existing.overlap(&impl_, self) {
+                    return Err(TypeError::Overlap {
+                        first: Box::new(existing),
+                        second: Box::new(impl_),
+                        ty,
+                    });
+                }
+            }
+        }
         self.impls.push(impl_);
+        Ok(())
     }
 
     pub fn new_type_var(&mut self) -> u32 {
@@ -57,6 +169,16 @@ impl TypeSystem {
         id
     }
 
+    /// The type var `$written` stands for, which is a new one the first time it's written.
+    pub fn written_var(&mut self, written: u32) -> u32 {
+        if let Some(id) = self.written_vars.get(&written) {
+            return *id;
+        }
+        let id = self.new_type_var();
+        self.written_vars.insert(written, id);
+        id
+    }
+
     pub fn resolve(&self, type_var: u32) -> Option<Type> {
         match self.type_vars.get(&type_var).unwrap() {
             Type::Free => None,
@@ -64,6 +186,43 @@ impl TypeSystem {
         }
     }
 
+    pub fn error(&mut self, error: TypeError) {
+        info!("Error: {}", error);
+        if !self.errors.contains(&error) {
+            self.errors.push(error);
+        }
+    }
+
+    /// Whether a cycle through this goal may be taken as proof of it.
+    pub fn is_coinductive(&self, goal: &Stmt) -> bool {
+        match goal {
+            Stmt::Extends {
+                super_: Type::Named(named),
+                ..
+            } => self.decls.get(&named.name).is_some_and(|decl| decl.auto),
+            _ => false,
+        }
+    }
+
+    pub fn snapshot(&self) -> HashMap<u32, Type> {
+        self.type_vars.clone()
+    }
+
+    /// Rolls the type vars back to a snapshot, dropping the vars created since.
+    ///
+    /// Their ids aren't handed out again, so anything which kept hold of one of
+    /// them can `revive` it.
+    pub fn restore(&mut self, snapshot: HashMap<u32, Type>) {
+        self.type_vars = snapshot;
+    }
+
+    /// Brings back, freed, any of `vars` which were dropped by a restore.
+    pub fn revive(&mut self, vars: impl IntoIterator<Item = u32>) {
+        for id in vars {
+            self.type_vars.entry(id).or_insert(Type::Free);
+        }
+    }
+
     pub fn add_goal(&mut self, goal: Logic) {
         let goal = match &self.goal {
             Logic::True => goal,
@@ -0,0 +1,81 @@
+use std::{collections::HashMap, fmt::Display};
+
+use crate::error::TypeError;
+
+use super::{args::GeneircArgs, Named, Type};
+
+/// A name for another type, such as `type StrMap[V] = Map[String, V]`.
+#[derive(Debug, Clone)]
+pub struct Alias {
+    pub name: String,
+    pub args: GeneircArgs,
+    pub ty: Type,
+}
+
+impl Alias {
+    /// The aliased type with the alias's parameters replaced by `args`.
+    pub fn expand(&self, args: &[Type]) -> Result<Type, TypeError> {
+        if args.len() != self.args.len() {
+            return Err(TypeError::Arity {
+                name: self.name.clone(),
+                min: self.args.len(),
+                max: self.args.len(),
+                found: args.len(),
+            });
+        }
+        let params = self
+            .args
+            .iter()
+            .cloned()
+            .zip(args.iter().cloned())
+            .collect::<HashMap<_, _>>();
+        Ok(self.ty.parameterise(&params))
+    }
+}
+
+impl Type {
+    /// This type with every alias in it replaced by what it expands to, so that
+    /// types which only differ in how they're written compare equal.
+    pub fn expand_aliases(&self) -> Type {
+        match self {
+            Type::Alias { ty, .. } => ty.expand_aliases(),
+            Type::Named(named) => Type::Named(named.expand_aliases()),
+            Type::Generic(name, args) => Type::Generic(
+                name.clone(),
+                args.iter().map(Type::expand_aliases).collect(),
+            ),
+            Type::Projection { ty, decl, name } => Type::Projection {
+                ty: Box::new(ty.expand_aliases()),
+                decl: decl.expand_aliases(),
+                name: name.clone(),
+            },
+            Type::Function { params, ret } => Type::Function {
+                params: params.iter().map(Type::expand_aliases).collect(),
+                ret: Box::new(ret.expand_aliases()),
+            },
+            Type::Tuple(_) | Type::Union(_) | Type::Intersection(_) => {
+                self.map_members(Type::expand_aliases)
+            }
+            _ => self.clone(),
+        }
+    }
+}
+
+impl Named {
+    pub fn expand_aliases(&self) -> Named {
+        Named {
+            name: self.name.clone(),
+            args: self.args.iter().map(Type::expand_aliases).collect(),
+        }
+    }
+}
+
+impl Display for Alias {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "type {}", self.name)?;
+        if !self.args.is_empty() {
+            write!(f, "[{}]", self.args.join(", "))?;
+        }
+        write!(f, " = {}", self.ty)
+    }
+}
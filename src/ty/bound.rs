@@ -2,10 +2,11 @@ use std::{collections::HashMap, fmt::Display};
 
 use crate::{
     logic::{stmt::Stmt, Logic},
+    state::TypeSystem,
     ty::Type,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Bound {
     pub sub: Type,
     pub super_: Type,
@@ -18,6 +19,13 @@ impl Bound {
             super_: self.super_.parameterise(params),
         }
     }
+
+    pub fn resolve(&self, state: &TypeSystem) -> Bound {
+        Bound {
+            sub: self.sub.resolve(state),
+            super_: self.super_.resolve(state),
+        }
+    }
 }
 
 impl From<Bound> for Logic {
@@ -0,0 +1,111 @@
+use crate::{error::TypeError, state::TypeSystem};
+
+use super::{impl_::Impl, Type};
+
+impl Impl {
+    /// The type both impls apply to, if their heads can be unified.
+    ///
+    /// Where-clauses aren't taken into account, so impls which only differ in
+    /// their bounds are still reported as overlapping.
+    pub fn overlap(&self, other: &Impl, state: &mut TypeSystem) -> Option<Type> {
+        if self.to.name != other.to.name {
+            return None;
+        }
+        let snapshot = state.snapshot();
+        let first = self.freshen(state);
+        let second = other.freshen(state);
+        let from = Type::Named(first.from);
+        let overlap = (from.unify(&Type::Named(second.from), state)
+            && Type::Named(first.to).unify(&Type::Named(second.to), state))
+        .then(|| from.resolve(state));
+        state.restore(snapshot);
+        overlap
+    }
+}
+
+impl TypeSystem {
+    /// Every pair of impls which could both apply to the same type.
+    pub fn overlaps(&mut self) -> Vec<TypeError> {
+        let impls = self.impls.clone();
+        let mut errors = vec![];
+        for (index, first) in impls.iter().enumerate() {
+            for second in &impls[index + 1..] {
+                if let Some(ty) = first.overlap(second, self) {
+                    errors.push(TypeError::Overlap {
+                        first: Box::new(first.clone()),
+                        second: Box::new(second.clone()),
+                        ty,
+                    });
+                }
+            }
+        }
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{extra::SimpleState, Parser};
+
+    use crate::{
+        error::TypeError,
+        parser::{lexer::lex, test_parser},
+        state::TypeSystem,
+        ty::{Named, Type},
+    };
+
+    const INPUT: &str = include_str!("../../tests/overlap.type");
+
+    fn parse(ts: TypeSystem) -> (Option<TypeSystem>, Vec<String>) {
+        let mut state = SimpleState::from(ts);
+        let (test, errors) = test_parser()
+            .parse_with_state(lex(INPUT), &mut state)
+            .into_output_errors();
+        (
+            test.map(|test| test.ts),
+            errors.iter().map(|error| error.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn test_overlap() {
+        let (Some(mut ts), _) = parse(TypeSystem::new()) else {
+            panic!("Expected a type system");
+        };
+        let [generic, list_int, bool] = ts.impls.clone().try_into().unwrap();
+        assert_eq!(
+            generic.overlap(&list_int, &mut ts),
+            Some(Type::Named(Named {
+                name: "List".to_string(),
+                args: vec![Type::Named(Named {
+                    name: "Int".to_string(),
+                    args: vec![],
+                })],
+            }))
+        );
+        assert_eq!(list_int.overlap(&bool, &mut ts), None);
+        assert_eq!(generic.overlap(&bool, &mut ts), None);
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let (Some(mut ts), _) = parse(TypeSystem::new()) else {
+            panic!("Expected a type system");
+        };
+        let overlaps = ts.overlaps();
+        assert!(matches!(
+            &overlaps[..],
+            [TypeError::Overlap { first, second, .. }]
+                if first.from.name == "List" && second.from.args.len() == 1
+        ));
+    }
+
+    #[test]
+    fn test_deny_overlap() {
+        let mut ts = TypeSystem::new();
+        ts.deny_overlap = true;
+        let (_, errors) = parse(ts);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("conflicting impls"));
+    }
+}
@@ -1,33 +1,150 @@
 use core::fmt;
-use std::fmt::Display;
+use std::{collections::HashMap, fmt::Display};
 
-use crate::ty::args::GeneircArgsExt as _;
+use crate::error::TypeError;
 
-use super::{args::GeneircArgs, bound::Bound};
+use super::{args::GeneircArgs, bound::Bound, variance::Variance, Named, Type};
 
 #[derive(Debug, Clone)]
 pub struct Decl {
     pub name: String,
+    /// Whether goals on this decl are proven coinductively, so cycles back to them hold.
+    pub auto: bool,
     pub args: GeneircArgs,
+    /// The variance of each of `args`, in the same order.
+    pub variance: Vec<Variance>,
+    /// How many arguments each of `args` takes, which is zero unless it's a type constructor.
+    pub kinds: Vec<usize>,
+    /// The default for each of `args`, used when it's omitted from the end.
+    pub defaults: Vec<Option<Type>>,
     pub bounds: Vec<Bound>,
+    pub members: Vec<(String, Type)>,
+    /// The associated types which each impl of this decl must bind.
+    pub assoc: Vec<String>,
+}
+
+impl Decl {
+    pub fn member(&self, name: &str) -> Option<&Type> {
+        self.members
+            .iter()
+            .find(|(member, _)| member == name)
+            .map(|(_, ty)| ty)
+    }
+
+    /// Adds the defaults for any arguments omitted from the end of `named`.
+    pub fn apply_defaults(&self, named: &Named) -> Result<Named, TypeError> {
+        self.check_arity(named)?;
+        let found = named.args.len();
+        let mut params = self
+            .args
+            .iter()
+            .cloned()
+            .zip(named.args.iter().cloned())
+            .collect::<HashMap<_, _>>();
+        let mut args = named.args.clone();
+        for (param, default) in self.args.iter().zip(&self.defaults).skip(found) {
+            let Some(default) = default else {
+                break;
+            };
+            let arg = default.parameterise(&params);
+            params.insert(param.clone(), arg.clone());
+            args.push(arg);
+        }
+        Ok(Named {
+            name: named.name.clone(),
+            args,
+        })
+    }
+
+    /// Checks that `named` gives this decl no more arguments than it has, and that
+    /// it only leaves out those with defaults.
+    pub fn check_arity(&self, named: &Named) -> Result<(), TypeError> {
+        let max = self.args.len();
+        let min = max
+            - self
+                .defaults
+                .iter()
+                .filter(|default| default.is_some())
+                .count();
+        let found = named.args.len();
+        if found > max || found < min {
+            return Err(TypeError::Arity {
+                name: self.name.clone(),
+                min,
+                max,
+                found,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn kind(&self, index: usize) -> usize {
+        self.kinds.get(index).copied().unwrap_or_default()
+    }
+
+    pub fn variance(&self, index: usize) -> Variance {
+        self.variance
+            .get(index)
+            .copied()
+            .unwrap_or(Variance::Invariant)
+    }
 }
 
 impl Display for Decl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.auto {
+            write!(f, "auto ")?;
+        }
+        write!(f, "{}", self.name)?;
+        if !self.args.is_empty() {
+            write!(
+                f,
+                "[{}]",
+                self.args
+                    .iter()
+                    .enumerate()
+                    .map(|(index, arg)| {
+                        let mut param = format!("{}{arg}", self.variance(index));
+                        if self.kind(index) > 0 {
+                            param
+                                .push_str(&format!("[{}]", vec!["_"; self.kind(index)].join(", ")));
+                        }
+                        if let Some(Some(default)) = self.defaults.get(index) {
+                            param.push_str(&format!(" = {default}"));
+                        }
+                        param
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
+        }
         if !self.bounds.is_empty() {
             write!(
                 f,
-                "{}{} where {}",
-                self.name,
-                self.args.get_string(),
+                " where {}",
                 self.bounds
                     .iter()
                     .map(Bound::to_string)
                     .collect::<Vec<_>>()
                     .join(", ")
-            )
-        } else {
-            write!(f, "{}{}", self.name, self.args.get_string())
+            )?;
+        }
+        if !self.members.is_empty() || !self.assoc.is_empty() {
+            write!(
+                f,
+                " {{ {} }}",
+                self.assoc
+                    .iter()
+                    .map(|name| format!("type {name}"))
+                    .chain(
+                        self.members
+                            .iter()
+                            .map(|(name, ty)| format!("{name}: {ty}"))
+                    )
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
         }
+        Ok(())
     }
 }
@@ -0,0 +1,114 @@
+use std::fmt::Display;
+
+use crate::{
+    logic::{stmt::Stmt, Logic},
+    state::TypeSystem,
+};
+
+use super::Type;
+
+/// A type to fall back on for a type var which is still free once the goals
+/// stop making progress, such as `default IntLiteral = Int`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fallback {
+    pub target: Target,
+    pub ty: Type,
+}
+
+/// Which free type vars a fallback applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    /// Just this one.
+    Var(u32),
+    /// Those a goal is waiting on to extend the named decl.
+    Bound(String),
+}
+
+impl Fallback {
+    fn applies(&self, id: u32, waiting: &[Stmt]) -> bool {
+        match &self.target {
+            Target::Var(var) => *var == id,
+            Target::Bound(name) => waiting.iter().any(|stmt| match stmt {
+                Stmt::Extends {
+                    sub: Type::Var(var),
+                    super_,
+                } => {
+                    *var == id
+                        && matches!(super_.unalias(), Type::Named(named) if &named.name == name)
+                }
+                _ => false,
+            }),
+        }
+    }
+}
+
+impl TypeSystem {
+    /// Binds each free type var `logic` is waiting on to the first fallback which
+    /// applies to it, returning whether any were bound.
+    pub fn apply_fallbacks(&mut self, logic: &Logic) -> bool {
+        let logic = logic.resolve(self);
+        let waiting = logic.stmts();
+        let mut applied = false;
+        for id in logic.vars() {
+            if self.resolve(id).is_some() {
+                continue;
+            }
+            let Some(fallback) = self
+                .fallbacks
+                .iter()
+                .find(|fallback| fallback.applies(id, &waiting))
+            else {
+                continue;
+            };
+            self.type_vars.insert(id, fallback.ty.clone());
+            applied = true;
+        }
+        applied
+    }
+}
+
+impl Display for Fallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.target {
+            Target::Var(id) => write!(f, "default ${id} = {}", self.ty),
+            Target::Bound(name) => write!(f, "default {name} = {}", self.ty),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{
+        logic::Logic,
+        parser::{named, parse_test},
+    };
+
+    #[test]
+    fn test_first_fallback_applies() {
+        let input = "
+            Int;
+            Float;
+            IntLiteral;
+            impl [] IntLiteral for Int;
+            impl [] IntLiteral for Float;
+            default $1 = Float;
+            default IntLiteral = Int;
+            default IntLiteral = Float;
+            ---
+            $0: IntLiteral;
+            $1: IntLiteral;
+            ---
+            true
+        ";
+        let test = parse_test(input);
+        let mut ts = test.ts;
+        let goals = Logic::from(test.goals.clone()).reduce_to_fixpoint(&mut ts, true);
+        let (first, second) = (ts.written_vars[&0], ts.written_vars[&1]);
+        assert_eq!(ts.resolve(first), None);
+        assert!(ts.apply_fallbacks(&goals));
+        assert_eq!(ts.resolve(first), Some(named("Int")));
+        assert_eq!(ts.resolve(second), Some(named("Float")));
+        assert!(!ts.apply_fallbacks(&goals));
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use tracing::info;
+
+use crate::{
+    error::TypeError,
+    logic::{stmt::Stmt, Logic},
+    state::TypeSystem,
+};
+
+use super::{select::Candidate, Named, Type};
+
+impl Type {
+    pub fn has_member(
+        &self,
+        member: &str,
+        member_ty: &Type,
+        state: &mut TypeSystem,
+        infer: bool,
+    ) -> Logic {
+        match self {
+            Type::Named(named) => named.has_member(member, member_ty, state, infer),
+            Type::Alias { ty, .. } => ty.has_member(member, member_ty, state, infer),
+            Type::Unknown => Logic::True,
+            Type::Var(id) => match state.type_vars.get(id).unwrap() {
+                Type::Free => Logic::Stmt(Stmt::HasMember {
+                    ty: self.clone(),
+                    member: member.to_string(),
+                    member_ty: member_ty.clone(),
+                }),
+                ty => ty.clone().has_member(member, member_ty, state, infer),
+            },
+            Type::Applied(..) => match self.resolve(state) {
+                Type::Applied(..) => Logic::Stmt(Stmt::HasMember {
+                    ty: self.clone(),
+                    member: member.to_string(),
+                    member_ty: member_ty.clone(),
+                }),
+                ty => ty.has_member(member, member_ty, state, infer),
+            },
+            Type::Projection { .. } => match self.normalize(state, infer) {
+                Some((ty, logic)) => {
+                    vec![logic, ty.has_member(member, member_ty, state, infer)].into()
+                }
+                None => Logic::Stmt(Stmt::HasMember {
+                    ty: self.clone(),
+                    member: member.to_string(),
+                    member_ty: member_ty.clone(),
+                }),
+            },
+            Type::Generic(..) => {
+                let logics = self
+                    .bounds_in_scope(state)
+                    .iter()
+                    .map(|super_| super_.has_member(member, member_ty, state, infer))
+                    .collect::<Vec<_>>();
+                Logic::any(logics)
+            }
+            _ => Logic::False,
+        }
+    }
+}
+
+impl Named {
+    pub fn has_member(
+        &self,
+        member: &str,
+        member_ty: &Type,
+        state: &mut TypeSystem,
+        infer: bool,
+    ) -> Logic {
+        let Ok(paths) = self.paths_to_member(member, state) else {
+            let stmt = Stmt::HasMember {
+                ty: Type::Named(self.clone()),
+                member: member.to_string(),
+                member_ty: member_ty.clone(),
+            };
+            state.error(TypeError::Overflow(Box::new(stmt.clone())));
+            return Logic::Stmt(stmt);
+        };
+        info!("Found {} paths from {} to '{}'", paths.len(), self, member);
+        let candidates = Type::Named(self.clone()).candidates(paths, state, |ty, state| {
+            let Type::Named(named) = ty else {
+                return None;
+            };
+            named
+                .member_ty(member, state)
+                .unify(member_ty, state)
+                .then(Vec::new)
+        });
+        Candidate::select(candidates, state, infer)
+    }
+
+    /// The declared type of `member` on this type's decl, with the decl's
+    /// parameters replaced by this type's arguments.
+    fn member_ty(&self, member: &str, state: &mut TypeSystem) -> Type {
+        let decl = state.decls.get(&self.name).unwrap().clone();
+        let mut params = HashMap::new();
+        for (index, param) in decl.args.iter().enumerate() {
+            let arg = match self.args.get(index) {
+                Some(arg) => arg.clone(),
+                None => Type::Var(state.new_type_var()),
+            };
+            params.insert(param.clone(), arg);
+        }
+        decl.member(member)
+            .expect("Known to have member at this point")
+            .parameterise(&params)
+    }
+}
@@ -1,80 +1,213 @@
 use core::fmt;
 use std::{collections::HashMap, fmt::Display};
 
-use crate::ty::args::GeneircArgsExt;
+use crate::{error::TypeError, logic::Logic, state::TypeSystem};
 
 use super::{args::GeneircArgs, bound::Bound, Named, Type};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Impl {
     pub args: GeneircArgs,
+    /// How many arguments each generic takes when it's a type constructor, or
+    /// zero when it's a type.
+    pub kinds: Vec<usize>,
     pub from: Named,
     pub to: Named,
     pub bounds: Vec<Bound>,
+    /// The types bound to the associated types of the decl being implemented.
+    pub assoc: Vec<(String, Type)>,
 }
 
 impl Display for Impl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "impl {}{} for {}",
-            self.args.get_string(),
+            "impl [{}] {} for {}",
+            self.args
+                .iter()
+                .enumerate()
+                .map(|(index, arg)| match self.kind(index) {
+                    0 => arg.clone(),
+                    kind => format!("{arg}[{}]", vec!["_"; kind].join(", ")),
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
             self.to,
             self.from
-        )
+        )?;
+        if !self.assoc.is_empty() {
+            write!(
+                f,
+                " {{ {} }}",
+                self.assoc
+                    .iter()
+                    .map(|(name, ty)| format!("type {name} = {ty}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
+        }
+        Ok(())
     }
 }
 
 impl Impl {
-    pub fn map(&self, ty: &Named) -> Option<(Named, Vec<Bound>)> {
-        let mut params = HashMap::new();
-        if self.from.imply_generic_params(ty, &mut params) {
-            Some((
-                self.to.parameterise(&params),
-                self.bounds
-                    .iter()
-                    .map(|b| b.parameterise(&params))
-                    .collect(),
-            ))
-        } else {
-            None
+    /// Replaces the impl's generic args with new type vars.
+    pub fn freshen(&self, state: &mut TypeSystem) -> Impl {
+        let params = self
+            .args
+            .iter()
+            .map(|arg| (arg.clone(), Type::Var(state.new_type_var())))
+            .collect::<HashMap<_, _>>();
+        Impl {
+            args: vec![],
+            kinds: vec![],
+            from: self.from.parameterise(&params),
+            to: self.to.parameterise(&params),
+            bounds: self
+                .bounds
+                .iter()
+                .map(|b| b.parameterise(&params))
+                .collect(),
+            assoc: self
+                .assoc
+                .iter()
+                .map(|(name, ty)| (name.clone(), ty.parameterise(&params)))
+                .collect(),
         }
     }
-}
 
-impl Type {
-    fn imply_generic_params(&self, from: &Type, params: &mut HashMap<String, Type>) -> bool {
-        match (self, from) {
-            (Type::Named(this), Type::Named(from)) => this.imply_generic_params(from, params),
-            (Type::Generic(id), _) => {
-                if let Some(existing) = params.get(id) {
-                    if existing != from {
-                        return false;
+    /// Checks the impl's associated types, and the where-clauses of the decls in
+    /// its head with the impl's own where-clauses in scope.
+    pub fn check(&self, state: &mut TypeSystem) -> Result<(), TypeError> {
+        self.check_kinds(state)?;
+        self.check_assoc(state)?;
+        let scope = state.bounds.len();
+        state.bounds.extend(self.bounds.iter().cloned());
+        let unsatisfied = [&self.from, &self.to].into_iter().find_map(|head| {
+            let decl = state.decls.get(&head.name)?.clone();
+            if decl.args.len() != head.args.len() {
+                return None;
+            }
+            let params = decl
+                .args
+                .iter()
+                .cloned()
+                .zip(head.args.iter().cloned())
+                .collect::<HashMap<_, _>>();
+            decl.bounds
+                .iter()
+                .map(|bound| bound.parameterise(&params))
+                .find(|bound| {
+                    Logic::from(bound.clone()).reduce_to_fixpoint(state, false) == Logic::False
+                })
+                .map(|bound| (head.clone(), bound))
+        });
+        state.bounds.truncate(scope);
+        match unsatisfied {
+            Some((head, bound)) => Err(TypeError::Unsatisfied {
+                impl_: Box::new(self.clone()),
+                head: head.to_string(),
+                bound: Box::new(bound),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    pub fn kind(&self, index: usize) -> usize {
+        self.kinds.get(index).copied().unwrap_or_default()
+    }
+
+    /// Checks that each of the impl's generics given to a decl in its head takes
+    /// as many arguments as the decl's parameter does.
+    fn check_kinds(&self, state: &TypeSystem) -> Result<(), TypeError> {
+        for head in [&self.from, &self.to] {
+            let Some(decl) = state.decls.get(&head.name) else {
+                continue;
+            };
+            for (index, arg) in head.args.iter().enumerate() {
+                let Type::Generic(name, args) = arg else {
+                    continue;
+                };
+                let Some(position) = self.args.iter().position(|it| it == name) else {
+                    continue;
+                };
+                let (expected, found) = (decl.kind(index), self.kind(position));
+                if !args.is_empty() || expected == found {
+                    continue;
+                }
+                return Err(if expected == 0 {
+                    TypeError::Arity {
+                        name: name.clone(),
+                        min: found,
+                        max: found,
+                        found: 0,
                     }
-                    true
                 } else {
-                    params.insert(id.clone(), from.clone());
-                    true
-                }
+                    TypeError::Kind {
+                        ty: arg.clone(),
+                        kind: expected,
+                    }
+                });
             }
-            _ => self == from,
         }
+        Ok(())
     }
-}
 
-impl Named {
-    pub fn imply_generic_params(&self, from: &Named, params: &mut HashMap<String, Type>) -> bool {
-        if self.name != from.name {
-            return false;
+    /// Checks that the impl binds each associated type of the decl it implements,
+    /// and nothing else.
+    fn check_assoc(&self, state: &TypeSystem) -> Result<(), TypeError> {
+        let Some(decl) = state.decls.get(&self.to.name) else {
+            return Ok(());
+        };
+        if let Some((name, _)) = self
+            .assoc
+            .iter()
+            .find(|(name, _)| !decl.assoc.contains(name))
+        {
+            return Err(TypeError::UnknownAssoc {
+                decl: decl.name.clone(),
+                name: name.clone(),
+            });
         }
-        if self.args.len() != from.args.len() {
-            return false;
+        match decl
+            .assoc
+            .iter()
+            .find(|name| !self.assoc.iter().any(|(bound, _)| bound == *name))
+        {
+            Some(name) => Err(TypeError::MissingAssoc {
+                impl_: Box::new(self.clone()),
+                name: name.clone(),
+            }),
+            None => Ok(()),
         }
-        for (arg, from_arg) in self.args.iter().zip(from.args.iter()) {
-            if !arg.imply_generic_params(from_arg, params) {
-                return false;
-            }
+    }
+
+    /// Unifies a fresh copy of the impl's head with `ty`, binding type vars in `state`.
+    pub fn map(&self, ty: &Type, state: &mut TypeSystem) -> Option<Impl> {
+        let fresh = self.freshen(state);
+        if ty.unify(&Type::Named(fresh.from.clone()), state) {
+            Some(fresh)
+        } else {
+            None
+        }
+    }
+
+    /// Whether this impl's head is an instance of the other's, but not the other way around.
+    pub fn is_more_specific(&self, other: &Impl, state: &mut TypeSystem) -> bool {
+        self.is_instance_of(other, state) && !other.is_instance_of(self, state)
+    }
+
+    /// Whether the other impl's head can be made into this one's, keeping this
+    /// impl's generics fixed.
+    fn is_instance_of(&self, other: &Impl, state: &mut TypeSystem) -> bool {
+        if self.to.name != other.to.name {
+            return false;
         }
-        true
+        let snapshot = state.snapshot();
+        let other = other.freshen(state);
+        let res = Type::Named(other.from).unify(&Type::Named(self.from.clone()), state)
+            && Type::Named(other.to).unify(&Type::Named(self.to.clone()), state);
+        state.restore(snapshot);
+        res
     }
 }
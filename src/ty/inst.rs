@@ -1,8 +1,6 @@
 use std::collections::HashMap;
 
-use tracing::info;
-
-use crate::state::TypeSystem;
+use crate::{error::TypeError, state::TypeSystem};
 
 use super::{Named, Type};
 
@@ -11,14 +9,18 @@ impl Type {
         match self {
             Type::Named(named) => {
                 let mut params: HashMap<String, Type> = HashMap::new();
-                let decl = state.decls.get(&named.name).unwrap().clone();
-                let mut args = if decl.args.len() < named.args.len() {
-                    info!("Too many arguments for {}, clipping!", named.name);
-                    named.args[..decl.args.len()].to_vec()
-                } else {
-                    named.args.clone()
+                let Some(decl) = state.decls.get(&named.name).cloned() else {
+                    state.error(TypeError::Unknown(named.name.clone()));
+                    return self.clone();
+                };
+                let mut args = match decl.apply_defaults(named) {
+                    Ok(named) => named.args,
+                    Err(error) => {
+                        state.error(error);
+                        named.args.iter().take(decl.args.len()).cloned().collect()
+                    }
                 };
-                let missing = decl.args.len() - named.args.len();
+                let missing = decl.args.len() - args.len();
                 for _ in 0..missing {
                     args.push(Type::Var(state.new_type_var()));
                 }
@@ -32,11 +34,27 @@ impl Type {
                     args,
                 })
             }
-            Type::Generic(name) => panic!(
+            Type::Generic(name, _) => panic!(
                 "Cannot instantiate generic type {} which doesn't have named parent",
                 name
             ),
-            Type::Var(_) => self.clone(),
+            Type::Function { params, ret } => Type::Function {
+                params: params.iter().map(|param| param.inst(state)).collect(),
+                ret: Box::new(ret.inst(state)),
+            },
+            Type::Alias { alias, ty } => Type::Alias {
+                alias: alias.clone(),
+                ty: Box::new(ty.inst(state)),
+            },
+            Type::Tuple(_) | Type::Union(_) | Type::Intersection(_) => {
+                self.map_members(|ty| ty.inst(state))
+            }
+            Type::Var(_)
+            | Type::Applied(..)
+            | Type::Projection { .. }
+            | Type::Any
+            | Type::Never
+            | Type::Unknown => self.clone(),
             Type::Free => panic!("Cannot instantiate free type"),
         }
     }
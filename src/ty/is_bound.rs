@@ -2,16 +2,89 @@ use std::fmt::Display;
 
 use tracing::info;
 
-use crate::{logic::Logic, state::TypeSystem};
+use crate::{
+    error::TypeError,
+    logic::{stmt::Stmt, Logic},
+    state::TypeSystem,
+};
 
-use super::{Named, Type};
+use super::{select::Candidate, Named, Type};
 
 impl Display for Type {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Type::Named(named) => write!(f, "{}", named),
-            Type::Generic(id) => write!(f, "{}", id),
+            Type::Generic(name, args) if args.is_empty() => write!(f, "{}", name),
+            Type::Generic(name, args) => write!(
+                f,
+                "{}[{}]",
+                name,
+                args.iter()
+                    .map(Type::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
             Type::Var(id) => write!(f, "${}", id),
+            Type::Applied(id, args) => write!(
+                f,
+                "${}[{}]",
+                id,
+                args.iter()
+                    .map(Type::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Type::Projection { ty, decl, name } => write!(f, "<{} as {}>::{}", ty, decl, name),
+            Type::Function { params, ret } => {
+                write!(
+                    f,
+                    "({}) -> ",
+                    params
+                        .iter()
+                        .map(Type::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )?;
+                match ret.as_ref() {
+                    Type::Union(_) | Type::Intersection(_) => write!(f, "({})", ret),
+                    _ => write!(f, "{}", ret),
+                }
+            }
+            Type::Tuple(tys) if tys.len() == 1 => write!(f, "({},)", tys[0]),
+            Type::Tuple(tys) => write!(
+                f,
+                "({})",
+                tys.iter()
+                    .map(Type::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Type::Union(tys) => write!(
+                f,
+                "{}",
+                tys.iter()
+                    .map(|ty| match ty {
+                        Type::Union(_) => format!("({ty})"),
+                        _ => ty.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            ),
+            Type::Intersection(tys) => write!(
+                f,
+                "{}",
+                tys.iter()
+                    .map(|ty| match ty {
+                        Type::Union(_) | Type::Intersection(_) => format!("({ty})"),
+                        _ => ty.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" & ")
+            ),
+            Type::Alias { alias, .. } => write!(f, "{}", alias),
+            Type::Any => write!(f, "Any"),
+            Type::Never => write!(f, "Never"),
+            Type::Unknown => write!(f, "<UNKNOWN>"),
             Type::Free => write!(f, "<FREE>"),
         }
     }
@@ -37,32 +110,219 @@ impl Display for Named {
 impl Type {
     pub fn is_bound_by(&self, other: &Type, state: &mut TypeSystem, infer: bool) -> Logic {
         match (self, other) {
-            (Type::Named(this), Type::Named(other)) => {
-                let paths = this.paths_to_sub_ty(other, state);
-                info!("Found {} paths from {} to {}", paths.len(), this, other);
-                let mut logics = vec![];
-                for impls in paths {
-                    let mut ty = this.clone();
-                    let mut path_logic = vec![];
-                    for impl_ in impls {
-                        let (named, bounds) =
-                            impl_.map(&ty).expect("Know to be mapped at this point");
-                        path_logic.extend(bounds.iter().cloned().map(|b| b.into()));
-                        ty = named;
-                    }
-                    path_logic.push(ty.is_exactly(other, state, infer));
-                    logics.push(Logic::AllOf(path_logic));
+            (_, Type::Any) | (Type::Never, _) => Logic::True,
+            (Type::Unknown, _) | (_, Type::Unknown) => Logic::True,
+            (Type::Alias { ty, .. }, _) => ty.is_bound_by(other, state, infer),
+            (_, Type::Alias { ty, .. }) => self.is_bound_by(ty, state, infer),
+            (Type::Var(id), _) if state.resolve(*id).is_some() => {
+                let sub = state.resolve(*id).unwrap();
+                sub.is_bound_by(other, state, infer)
+            }
+            (_, Type::Var(id)) if state.resolve(*id).is_some() => {
+                let super_ = state.resolve(*id).unwrap();
+                self.is_bound_by(&super_, state, infer)
+            }
+            // A constructor var is only known once it's bound, to a constructor which
+            // can then be applied.
+            (Type::Applied(..), _) | (_, Type::Applied(..)) => {
+                let (sub, super_) = (self.resolve(state), other.resolve(state));
+                if matches!(sub, Type::Applied(..)) || matches!(super_, Type::Applied(..)) {
+                    self.deferred_bound(other)
+                } else {
+                    sub.is_bound_by(&super_, state, infer)
                 }
-
-                if logics.is_empty() {
-                    Logic::False
-                } else if logics.len() == 1 {
-                    logics[0].clone()
+            }
+            (Type::Projection { .. }, _) | (_, Type::Projection { .. }) if self == other => {
+                Logic::True
+            }
+            (Type::Projection { .. }, _) => match self.normalize(state, infer) {
+                Some((sub, logic)) => vec![logic, sub.is_bound_by(other, state, infer)].into(),
+                None => self.deferred_bound(other),
+            },
+            (_, Type::Projection { .. }) => match other.normalize(state, infer) {
+                Some((super_, logic)) => {
+                    vec![logic, self.is_bound_by(&super_, state, infer)].into()
+                }
+                None => self.deferred_bound(other),
+            },
+            (Type::Named(this), Type::Named(super_)) => {
+                let Ok(paths) = this.paths_to_sub_ty(super_, state) else {
+                    return self.overflow(other, state);
+                };
+                info!("Found {} paths from {} to {}", paths.len(), this, super_);
+                let candidates =
+                    self.candidates(paths, state, |ty, state| ty.unify_variant(other, state));
+                Candidate::select(candidates, state, infer)
+            }
+            // A free var is only inferred from impl heads, and only once a single impl applies.
+            (Type::Var(_), Type::Named(super_)) => {
+                let Ok(paths) = super_.paths_from_any_sub_ty(state) else {
+                    return self.overflow(other, state);
+                };
+                info!("Found {} paths from {} to {}", paths.len(), self, super_);
+                let candidates =
+                    self.candidates(paths, state, |ty, state| ty.unify_variant(other, state));
+                if candidates.len() > 1 {
+                    self.deferred_bound(other)
                 } else {
-                    Logic::OneOf(logics)
+                    Candidate::select(candidates, state, infer)
+                }
+            }
+            (Type::Var(_), _) | (_, Type::Var(_)) => self.deferred_bound(other),
+            // A union must be bound by all of its members, and only one member of an
+            // intersection needs to be, and the other way around when they're the super.
+            (Type::Union(subs), _) => subs
+                .iter()
+                .map(|sub| sub.bound_part(other, state, infer))
+                .collect::<Vec<_>>()
+                .into(),
+            (_, Type::Intersection(supers)) => supers
+                .iter()
+                .map(|super_| self.bound_part(super_, state, infer))
+                .collect::<Vec<_>>()
+                .into(),
+            (_, Type::Union(supers)) => Logic::any(
+                supers
+                    .iter()
+                    .map(|super_| self.bound_part(super_, state, infer))
+                    .collect(),
+            ),
+            (Type::Intersection(subs), _) => Logic::any(
+                subs.iter()
+                    .map(|sub| sub.bound_part(other, state, infer))
+                    .collect(),
+            ),
+            (Type::Tuple(subs), Type::Tuple(supers)) => {
+                if subs.len() != supers.len() {
+                    return Logic::False;
+                }
+                subs.iter()
+                    .zip(supers)
+                    .map(|(sub, super_)| sub.bound_part(super_, state, infer))
+                    .collect::<Vec<_>>()
+                    .into()
+            }
+            (
+                Type::Function { params, ret },
+                Type::Function {
+                    params: super_params,
+                    ret: super_ret,
+                },
+            ) => {
+                if params.len() != super_params.len() {
+                    return Logic::False;
                 }
+                // Parameters are contravariant, so each super parameter must be bound by ours.
+                let mut logics = super_params
+                    .iter()
+                    .zip(params)
+                    .map(|(super_param, param)| super_param.bound_part(param, state, infer))
+                    .collect::<Vec<_>>();
+                logics.push(ret.bound_part(super_ret, state, infer));
+                logics.into()
             }
-            _ => todo!(),
+            (Type::Generic(first, args), Type::Generic(second, other_args)) if first == second => {
+                Type::all_exactly(args, other_args, state, infer)
+            }
+            (Type::Generic(..), _) => {
+                let logics = self
+                    .bounds_in_scope(state)
+                    .iter()
+                    .map(|super_| super_.is_bound_by(other, state, infer))
+                    .collect::<Vec<_>>();
+                Logic::any(logics)
+            }
+            _ => Logic::False,
+        }
+    }
+
+    /// Checks `self: other` for a part of a structural type. While tracing, it's
+    /// reduced as a goal of its own so that it shows up when explaining, and
+    /// otherwise it's checked directly.
+    fn bound_part(&self, other: &Type, state: &mut TypeSystem, infer: bool) -> Logic {
+        if state.trace.is_some() {
+            self.deferred_bound(other).reduce(state, infer)
+        } else {
+            self.is_bound_by(other, state, infer)
         }
     }
+
+    fn deferred_bound(&self, other: &Type) -> Logic {
+        Logic::Stmt(Stmt::Extends {
+            sub: self.clone(),
+            super_: other.clone(),
+        })
+    }
+
+    fn overflow(&self, other: &Type, state: &mut TypeSystem) -> Logic {
+        let stmt = self.deferred_bound(other);
+        if let Logic::Stmt(stmt) = &stmt {
+            state.error(TypeError::Overflow(Box::new(stmt.clone())));
+        }
+        stmt
+    }
+
+    /// The super types given to this type by the where-clauses in scope.
+    pub fn bounds_in_scope(&self, state: &TypeSystem) -> Vec<Type> {
+        state
+            .bounds
+            .iter()
+            .filter(|bound| &bound.sub == self)
+            .map(|bound| bound.super_.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        logic::Logic,
+        parser::named,
+        state::TypeSystem,
+        ty::{bound::Bound, Type},
+    };
+
+    #[test]
+    fn test_free_var_is_deferred() {
+        let mut state = TypeSystem::new();
+        let var = Type::Var(state.new_type_var());
+        let output = named("Int").is_bound_by(&var, &mut state, true);
+        assert!(matches!(output, Logic::Stmt(_)));
+    }
+
+    #[test]
+    fn test_free_var_may_be_the_super_type() {
+        let mut state = TypeSystem::new();
+        let id = state.new_type_var();
+        let output = Type::Var(id).is_bound_by(&named("Display"), &mut state, true);
+        assert_eq!(output, Logic::True);
+        assert_eq!(state.resolve(id), Some(named("Display")));
+    }
+
+    #[test]
+    fn test_bound_var_is_resolved() {
+        let mut state = TypeSystem::new();
+        let id = state.new_type_var();
+        state.type_vars.insert(id, named("Int"));
+        let output = Type::Var(id).is_bound_by(&named("Int"), &mut state, true);
+        assert_eq!(output, Logic::True);
+    }
+
+    #[test]
+    fn test_generic_in_scope() {
+        let mut state = TypeSystem::new();
+        state.bounds.push(Bound {
+            sub: Type::Generic("T".to_string(), vec![]),
+            super_: named("Display"),
+        });
+        let generic = Type::Generic("T".to_string(), vec![]);
+        assert_eq!(
+            generic.is_bound_by(&named("Display"), &mut state, true),
+            Logic::True
+        );
+        assert_eq!(
+            generic.is_bound_by(&named("Debug"), &mut state, true),
+            Logic::False
+        );
+    }
 }
@@ -9,6 +9,8 @@ impl Type {
     pub fn is_exactly(&self, other: &Type, state: &mut TypeSystem, infer: bool) -> Logic {
         match (self, other) {
             (_, Type::Var(id)) => match state.type_vars.get(id).unwrap() {
+                // A free var is exactly itself, however it was reached.
+                Type::Free if &self.resolve(state) == other => Logic::True,
                 Type::Free => Logic::Stmt(Stmt::Exactly {
                     ty: self.clone(),
                     is: other.clone(),
@@ -29,21 +31,89 @@ impl Type {
                 }
                 ty => ty.clone().is_exactly(other, state, infer),
             },
+            (Type::Unknown, _) | (_, Type::Unknown) => Logic::True,
+            (Type::Applied(..), _) | (_, Type::Applied(..)) => {
+                let (ty, is) = (self.resolve(state), other.resolve(state));
+                if !matches!(ty, Type::Applied(..)) && !matches!(is, Type::Applied(..)) {
+                    ty.is_exactly(&is, state, infer)
+                } else if !infer {
+                    Logic::Stmt(Stmt::Exactly {
+                        ty: self.clone(),
+                        is: other.clone(),
+                    })
+                } else if ty.unify(&is, state) {
+                    Logic::True
+                } else {
+                    Logic::False
+                }
+            }
+            (Type::Alias { ty, .. }, _) => ty.is_exactly(other, state, infer),
+            (_, Type::Alias { ty, .. }) => self.is_exactly(ty, state, infer),
+            (Type::Projection { .. }, _) | (_, Type::Projection { .. }) if self == other => {
+                Logic::True
+            }
+            (Type::Projection { .. }, _) => match self.normalize(state, infer) {
+                Some((ty, logic)) => vec![logic, ty.is_exactly(other, state, infer)].into(),
+                None => Logic::Stmt(Stmt::Exactly {
+                    ty: self.clone(),
+                    is: other.clone(),
+                }),
+            },
+            (_, Type::Projection { .. }) => match other.normalize(state, infer) {
+                Some((ty, logic)) => vec![logic, self.is_exactly(&ty, state, infer)].into(),
+                None => Logic::Stmt(Stmt::Exactly {
+                    ty: self.clone(),
+                    is: other.clone(),
+                }),
+            },
             (Type::Named(this), Type::Named(other)) => this.is_exactly(other, state, infer),
-            (Type::Generic(first), Type::Generic(second)) if first == second => Logic::True,
+            (
+                Type::Function { params, ret },
+                Type::Function {
+                    params: other_params,
+                    ret: other_ret,
+                },
+            ) => vec![
+                Type::all_exactly(params, other_params, state, infer),
+                ret.is_exactly(other_ret, state, infer),
+            ]
+            .into(),
+            (Type::Tuple(tys), Type::Tuple(others))
+            | (Type::Union(tys), Type::Union(others))
+            | (Type::Intersection(tys), Type::Intersection(others)) => {
+                Type::all_exactly(tys, others, state, infer)
+            }
+            (Type::Generic(first, args), Type::Generic(second, other_args)) if first == second => {
+                Type::all_exactly(args, other_args, state, infer)
+            }
+            (Type::Any, Type::Any) | (Type::Never, Type::Never) => Logic::True,
             _ => Logic::False,
         }
     }
+
+    /// Whether each type is exactly the one at the same position in `others`.
+    pub fn all_exactly(
+        tys: &[Type],
+        others: &[Type],
+        state: &mut TypeSystem,
+        infer: bool,
+    ) -> Logic {
+        if tys.len() != others.len() {
+            return Logic::False;
+        }
+        tys.iter()
+            .zip(others)
+            .map(|(ty, other)| ty.is_exactly(other, state, infer))
+            .collect::<Vec<_>>()
+            .into()
+    }
 }
 
 impl Named {
     pub fn is_exactly(&self, other: &Named, state: &mut TypeSystem, infer: bool) -> Logic {
-        if self.name != other.name {
+        if self.name != other.name || self.args.len() != other.args.len() {
             return Logic::False;
         }
-        if self.args.len() != other.args.len() {
-            panic!("Type args length mismatch");
-        }
         let mut logics = vec![];
         for (a, b) in self.args.iter().zip(other.args.iter()) {
             match a.is_exactly(b, state, infer) {
@@ -0,0 +1,183 @@
+use crate::{logic::Logic, state::TypeSystem};
+
+use super::Type;
+
+impl TypeSystem {
+    /// The most specific type which both types extend, found by following impls
+    /// up from `first`.
+    ///
+    /// When more than one such type is equally specific they are all kept as an
+    /// intersection, and when there are none the join is `Any`.
+    pub fn join(&mut self, first: &Type, second: &Type) -> Type {
+        if self.holds(first, second) {
+            return second.clone();
+        }
+        if self.holds(second, first) {
+            return first.clone();
+        }
+        let common = self
+            .reachable(first, true)
+            .into_iter()
+            .filter(|ty| self.holds(first, ty) && self.holds(second, ty))
+            .collect::<Vec<_>>();
+        let mut least = self.least(common, |state, sub, super_| state.holds(sub, super_));
+        match least.len() {
+            0 => Type::Any,
+            1 => least.pop().unwrap(),
+            _ => Type::Intersection(least),
+        }
+    }
+
+    /// The least specific type which extends both types, found by following impls
+    /// down from `first`.
+    ///
+    /// When more than one such type is equally general they are all kept as a
+    /// union, and when there are none the meet is `Never`.
+    pub fn meet(&mut self, first: &Type, second: &Type) -> Type {
+        if self.holds(first, second) {
+            return first.clone();
+        }
+        if self.holds(second, first) {
+            return second.clone();
+        }
+        let common = self
+            .reachable(first, false)
+            .into_iter()
+            .filter(|ty| self.holds(ty, first) && self.holds(ty, second))
+            .collect::<Vec<_>>();
+        let mut greatest = self.least(common, |state, sub, super_| state.holds(super_, sub));
+        match greatest.len() {
+            0 => Type::Never,
+            1 => greatest.pop().unwrap(),
+            _ => Type::Union(greatest),
+        }
+    }
+
+    /// Whether `sub: super_` holds without binding any type vars.
+    fn holds(&mut self, sub: &Type, super_: &Type) -> bool {
+        let snapshot = self.snapshot();
+        let res = sub
+            .is_bound_by(super_, self, false)
+            .reduce_to_fixpoint(self, false);
+        self.restore(snapshot);
+        res == Logic::True
+    }
+
+    /// Keeps only the types which no other type is strictly below, dropping repeats.
+    fn least(
+        &mut self,
+        tys: Vec<Type>,
+        below: impl Fn(&mut TypeSystem, &Type, &Type) -> bool,
+    ) -> Vec<Type> {
+        let mut res: Vec<Type> = vec![];
+        for ty in &tys {
+            let dominated = tys
+                .iter()
+                .any(|other| below(self, other, ty) && !below(self, ty, other));
+            let repeated = res.iter().any(|kept| below(self, kept, ty));
+            if !dominated && !repeated {
+                res.push(ty.clone());
+            }
+        }
+        res
+    }
+
+    /// Every type reached from `ty` by following impls up to their decl, or down
+    /// to the types they're for when `up` is false, starting with `ty` itself.
+    ///
+    /// Impls which would leave a type var in the type reached are skipped.
+    fn reachable(&mut self, ty: &Type, up: bool) -> Vec<Type> {
+        let mut reached = vec![ty.clone()];
+        let mut frontier = vec![ty.clone()];
+        for _ in 0..self.recursion_limit {
+            let mut next = vec![];
+            for ty in &frontier {
+                for impl_ in self.impls.clone() {
+                    let snapshot = self.snapshot();
+                    let fresh = impl_.freshen(self);
+                    let (start, end) = if up {
+                        (fresh.from, fresh.to)
+                    } else {
+                        (fresh.to, fresh.from)
+                    };
+                    let found = ty
+                        .unify(&Type::Named(start), self)
+                        .then(|| Type::Named(end).resolve(self));
+                    self.restore(snapshot);
+                    if let Some(found) = found {
+                        let seen = reached
+                            .iter()
+                            .any(|ty| ty.expand_aliases() == found.expand_aliases());
+                        if found.vars().is_empty() && !seen {
+                            reached.push(found.clone());
+                            next.push(found);
+                        }
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+        reached
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{
+        logic::{stmt::Stmt, Logic},
+        parser::{named, parse_test},
+        state::TypeSystem,
+        ty::Type,
+    };
+
+    const ENV: &str = "
+        Animal;
+        Pet;
+        Cat;
+        Dog;
+        Lion;
+        impl [] Animal for Cat;
+        impl [] Animal for Dog;
+        impl [] Animal for Lion;
+        impl [] Pet for Cat;
+        impl [] Pet for Dog;
+        ---
+    ";
+
+    /// Runs `op` on the two sides of the test's goal.
+    fn run(goal: &str, op: fn(&mut TypeSystem, &Type, &Type) -> Type) -> Type {
+        let input = format!("{ENV}{goal}; --- true");
+        let test = parse_test(&input);
+        let mut ts = test.ts;
+        let Some(Logic::Stmt(Stmt::Extends { sub, super_ })) = test.goals.first() else {
+            panic!("Expected Bound");
+        };
+        op(&mut ts, sub, super_)
+    }
+
+    #[test]
+    fn test_join() {
+        assert_eq!(run("Cat: Lion", |ts, a, b| ts.join(a, b)), named("Animal"));
+        assert_eq!(
+            run("Cat: Dog", |ts, a, b| ts.join(a, b)),
+            Type::Intersection(vec![named("Animal"), named("Pet")])
+        );
+        assert_eq!(
+            run("Cat: Animal", |ts, a, b| ts.join(a, b)),
+            named("Animal")
+        );
+    }
+
+    #[test]
+    fn test_meet() {
+        assert_eq!(
+            run("Animal: Pet", |ts, a, b| ts.meet(a, b)),
+            Type::Union(vec![named("Cat"), named("Dog")])
+        );
+        assert_eq!(run("Lion: Pet", |ts, a, b| ts.meet(a, b)), Type::Never);
+    }
+}
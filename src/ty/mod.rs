@@ -2,14 +2,23 @@ use std::collections::HashMap;
 
 use crate::state::TypeSystem;
 
+pub mod alias;
 pub mod args;
 pub mod bound;
+pub mod coherence;
 pub mod decl;
+pub mod fallback;
+pub mod has_member;
 pub mod impl_;
 pub mod inst;
 pub mod is_bound;
 pub mod is_exactly;
+pub mod lattice;
 pub mod path;
+pub mod project;
+pub mod select;
+pub mod unify;
+pub mod variance;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Named {
@@ -20,8 +29,40 @@ pub struct Named {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Type {
     Named(Named),
-    Generic(String),
+    /// A generic in scope, applied to arguments when it's a type constructor.
+    Generic(String, Vec<Type>),
     Var(u32),
+    /// A type var standing for a type constructor, applied to arguments, as a
+    /// constructor generic of an impl is once the impl is freshened.
+    Applied(u32, Vec<Type>),
+    /// An associated type of a decl, as bound by the impl of that decl for `ty`.
+    Projection {
+        ty: Box<Type>,
+        decl: Named,
+        name: String,
+    },
+    /// A function from its parameters to its return type.
+    Function {
+        params: Vec<Type>,
+        ret: Box<Type>,
+    },
+    /// A use of an alias, kept alongside what it expands to so it can still be shown.
+    Alias {
+        alias: Named,
+        ty: Box<Type>,
+    },
+    Tuple(Vec<Type>),
+    /// A type which is one of its members.
+    Union(Vec<Type>),
+    /// A type which is all of its members.
+    Intersection(Vec<Type>),
+    /// The top type, which every type extends.
+    Any,
+    /// The bottom type, which extends every type.
+    Never,
+    /// Stands in for a type var the solver got stuck on, so that checking can go
+    /// on. Everything holds of it, as the error has already been reported.
+    Unknown,
     Free,
 }
 
@@ -29,23 +70,156 @@ impl Type {
     pub fn parameterise(&self, params: &HashMap<String, Type>) -> Type {
         match self {
             Type::Named(named) => Type::Named(named.parameterise(params)),
-            Type::Generic(name) => params[name].clone(),
-            Type::Var(_) => self.clone(),
+            Type::Generic(name, args) if args.is_empty() => params[name].clone(),
+            // A constructor which isn't known yet can't be applied, so the
+            // application is left as it is.
+            Type::Generic(name, args) => {
+                let args = args.iter().map(|arg| arg.parameterise(params)).collect();
+                match &params[name] {
+                    Type::Named(named) if named.args.is_empty() => Type::Named(Named {
+                        name: named.name.clone(),
+                        args,
+                    }),
+                    Type::Generic(name, ctor_args) if ctor_args.is_empty() => {
+                        Type::Generic(name.clone(), args)
+                    }
+                    Type::Var(id) => Type::Applied(*id, args),
+                    _ => Type::Generic(name.clone(), args),
+                }
+            }
+            Type::Var(_) | Type::Any | Type::Never | Type::Unknown => self.clone(),
+            Type::Applied(id, args) => Type::Applied(
+                *id,
+                args.iter().map(|arg| arg.parameterise(params)).collect(),
+            ),
+            Type::Projection { ty, decl, name } => Type::Projection {
+                ty: Box::new(ty.parameterise(params)),
+                decl: decl.parameterise(params),
+                name: name.clone(),
+            },
+            Type::Function {
+                params: function_params,
+                ret,
+            } => Type::Function {
+                params: function_params
+                    .iter()
+                    .map(|param| param.parameterise(params))
+                    .collect(),
+                ret: Box::new(ret.parameterise(params)),
+            },
+            Type::Alias { alias, ty } => Type::Alias {
+                alias: alias.parameterise(params),
+                ty: Box::new(ty.parameterise(params)),
+            },
+            Type::Tuple(_) | Type::Union(_) | Type::Intersection(_) => {
+                self.map_members(|ty| ty.parameterise(params))
+            }
             Type::Free => panic!("Cannot parameterise free type"),
         }
     }
 
     pub fn resolve(&self, state: &TypeSystem) -> Type {
         match self {
-            Type::Named(Named { name: id, args }) => Type::Named(Named {
-                name: id.to_string(),
-                args: args.iter().map(|arg| arg.resolve(state)).collect(),
-            }),
-            Type::Var(id) => state.resolve(*id).unwrap_or_else(|| self.clone()),
+            Type::Named(named) => Type::Named(named.resolve(state)),
+            Type::Var(id) => state
+                .resolve(*id)
+                .map(|ty| ty.resolve(state))
+                .unwrap_or_else(|| self.clone()),
+            Type::Projection { ty, decl, name } => Type::Projection {
+                ty: Box::new(ty.resolve(state)),
+                decl: decl.resolve(state),
+                name: name.clone(),
+            },
+            Type::Function { params, ret } => Type::Function {
+                params: params.iter().map(|param| param.resolve(state)).collect(),
+                ret: Box::new(ret.resolve(state)),
+            },
+            Type::Alias { alias, ty } => Type::Alias {
+                alias: alias.resolve(state),
+                ty: Box::new(ty.resolve(state)),
+            },
+            Type::Tuple(_) | Type::Union(_) | Type::Intersection(_) => {
+                self.map_members(|ty| ty.resolve(state))
+            }
+            Type::Generic(name, args) => Type::Generic(
+                name.clone(),
+                args.iter().map(|arg| arg.resolve(state)).collect(),
+            ),
+            Type::Applied(id, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| arg.resolve(state))
+                    .collect::<Vec<_>>();
+                Type::Var(*id)
+                    .resolve(state)
+                    .apply(args.clone())
+                    .unwrap_or(Type::Applied(*id, args))
+            }
             Type::Free => panic!("Cannot resolve free type"),
             _ => self.clone(),
         }
     }
+
+    pub fn vars(&self) -> Vec<u32> {
+        match self {
+            Type::Named(named) => named.args.iter().flat_map(Type::vars).collect(),
+            Type::Var(id) => vec![*id],
+            Type::Projection { ty, decl, .. } => ty
+                .vars()
+                .into_iter()
+                .chain(decl.args.iter().flat_map(Type::vars))
+                .collect(),
+            Type::Function { params, ret } => params
+                .iter()
+                .chain([ret.as_ref()])
+                .flat_map(Type::vars)
+                .collect(),
+            Type::Alias { ty, .. } => ty.vars(),
+            Type::Tuple(tys) | Type::Union(tys) | Type::Intersection(tys) => {
+                tys.iter().flat_map(Type::vars).collect()
+            }
+            Type::Generic(_, args) => args.iter().flat_map(Type::vars).collect(),
+            Type::Applied(id, args) => [*id]
+                .into_iter()
+                .chain(args.iter().flat_map(Type::vars))
+                .collect(),
+            Type::Any | Type::Never | Type::Unknown | Type::Free => vec![],
+        }
+    }
+
+    /// Applies this type, as a type constructor, to `args`. Only a bare decl,
+    /// generic or type var can be applied.
+    pub fn apply(self, args: Vec<Type>) -> Option<Type> {
+        match self {
+            Type::Named(named) if named.args.is_empty() => Some(Type::Named(Named {
+                name: named.name,
+                args,
+            })),
+            Type::Generic(name, ctor_args) if ctor_args.is_empty() => {
+                Some(Type::Generic(name, args))
+            }
+            Type::Var(id) => Some(Type::Applied(id, args)),
+            _ => None,
+        }
+    }
+
+    /// The type an alias expands to, or this type if it isn't an alias.
+    pub fn unalias(&self) -> &Type {
+        match self {
+            Type::Alias { ty, .. } => ty.unalias(),
+            _ => self,
+        }
+    }
+
+    /// Rebuilds a tuple, union or intersection with each of its members mapped.
+    pub fn map_members(&self, f: impl FnMut(&Type) -> Type) -> Type {
+        match self {
+            Type::Tuple(tys) => Type::Tuple(tys.iter().map(f).collect()),
+            Type::Union(tys) => Type::Union(tys.iter().map(f).collect()),
+            Type::Intersection(tys) => Type::Intersection(tys.iter().map(f).collect()),
+            _ => self.clone(),
+        }
+    }
 }
 
 impl Named {
@@ -59,4 +233,11 @@ impl Named {
                 .collect(),
         }
     }
+
+    pub fn resolve(&self, state: &TypeSystem) -> Named {
+        Named {
+            name: self.name.to_string(),
+            args: self.args.iter().map(|arg| arg.resolve(state)).collect(),
+        }
+    }
 }
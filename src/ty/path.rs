@@ -2,21 +2,122 @@ use crate::state::TypeSystem;
 
 use super::{impl_::Impl, Named};
 
+/// A path of impls went deeper than `TypeSystem::recursion_limit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
 impl Named {
-    pub fn paths_to_sub_ty(&self, other: &Named, ts: &TypeSystem) -> Vec<Vec<Impl>> {
-        if self.name == other.name {
-            return vec![vec![]];
+    pub fn paths_to_sub_ty(
+        &self,
+        other: &Named,
+        ts: &TypeSystem,
+    ) -> Result<Vec<Vec<Impl>>, Overflow> {
+        self.paths(ts, &|name| name == other.name, &mut vec![], 0)
+    }
+
+    /// Paths to this type which start from any impl, for when the sub type isn't known yet.
+    ///
+    /// The empty path comes first, as the sub type may be this type itself.
+    pub fn paths_from_any_sub_ty(&self, ts: &TypeSystem) -> Result<Vec<Vec<Impl>>, Overflow> {
+        let mut paths = vec![vec![]];
+        for impl_ in &ts.impls {
+            let mut visited = vec![impl_.from.name.clone()];
+            for mut path in impl_
+                .to
+                .paths(ts, &|name| name == self.name, &mut visited, 1)?
+            {
+                path.insert(0, impl_.clone());
+                paths.push(path);
+            }
         }
-        ts.impls
-            .iter()
-            .filter(|impl_| impl_.from.name == self.name)
-            .flat_map(|impl_| {
-                let mut paths = impl_.to.paths_to_sub_ty(other, ts);
-                for path in paths.iter_mut() {
-                    path.insert(0, impl_.clone());
-                }
-                paths
-            })
-            .collect()
+        Ok(paths)
+    }
+
+    pub fn paths_to_member(
+        &self,
+        member: &str,
+        ts: &TypeSystem,
+    ) -> Result<Vec<Vec<Impl>>, Overflow> {
+        let has_member = |name: &str| {
+            ts.decls
+                .get(name)
+                .is_some_and(|decl| decl.member(member).is_some())
+        };
+        self.paths(ts, &has_member, &mut vec![], 0)
+    }
+
+    /// Follows impls out of this type until reaching one which is `done`.
+    ///
+    /// Types already on the current path are skipped, so cyclic impls end the
+    /// path rather than looping. `depth` is how many impls the path has followed
+    /// so far, and a path longer than the recursion limit is an overflow.
+    fn paths(
+        &self,
+        ts: &TypeSystem,
+        done: &impl Fn(&str) -> bool,
+        visited: &mut Vec<String>,
+        depth: usize,
+    ) -> Result<Vec<Vec<Impl>>, Overflow> {
+        if done(&self.name) {
+            return Ok(vec![vec![]]);
+        }
+        if depth >= ts.recursion_limit {
+            return Err(Overflow);
+        }
+        visited.push(self.name.clone());
+        let mut paths = vec![];
+        for impl_ in ts.impls.iter().filter(|impl_| impl_.from.name == self.name) {
+            if visited.contains(&impl_.to.name) {
+                continue;
+            }
+            for mut path in impl_.to.paths(ts, done, visited, depth + 1)? {
+                path.insert(0, impl_.clone());
+                paths.push(path);
+            }
+        }
+        visited.pop();
+        Ok(paths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{parser::parse_test, ty::Named};
+
+    use super::Overflow;
+
+    const INPUT: &str = "
+        A;
+        B;
+        C;
+        D;
+        impl [] B for A;
+        impl [] C for B;
+        impl [] D for C;
+        ---
+        ---
+        true
+    ";
+
+    fn paths(recursion_limit: usize) -> Result<usize, Overflow> {
+        let mut ts = parse_test(INPUT).ts;
+        ts.recursion_limit = recursion_limit;
+        let named = |name: &str| Named {
+            name: name.to_string(),
+            args: vec![],
+        };
+        let paths = named("A").paths_to_sub_ty(&named("D"), &ts)?;
+        Ok(paths.len())
+    }
+
+    #[test]
+    fn test_within_limit() {
+        assert_eq!(paths(3), Ok(1));
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(paths(2), Err(Overflow));
     }
 }
@@ -0,0 +1,82 @@
+use tracing::info;
+
+use crate::{error::TypeError, logic::Logic, state::TypeSystem};
+
+use super::Type;
+
+impl Type {
+    /// Replaces a projection with the type bound to it by the impl which applies,
+    /// along with what must hold for that impl to be used.
+    ///
+    /// Gives up while the projected type is unknown, or when more than one impl
+    /// could apply, so the projection can be retried once more is known. A name
+    /// the decl doesn't declare is an error, and never holds.
+    pub fn normalize(&self, state: &mut TypeSystem, infer: bool) -> Option<(Type, Logic)> {
+        let Type::Projection { ty, decl, name } = self else {
+            return None;
+        };
+        let declared = state
+            .decls
+            .get(&decl.name)
+            .is_some_and(|it| it.assoc.contains(name));
+        if !declared {
+            state.error(TypeError::UnknownAssoc {
+                decl: decl.name.clone(),
+                name: name.clone(),
+            });
+            return Some((Type::Unknown, Logic::False));
+        }
+        let (ty, inner) = match ty.resolve(state) {
+            ty @ Type::Projection { .. } => ty.normalize(state, infer)?,
+            ty => (ty, Logic::True),
+        };
+        let ty = ty.unalias().clone();
+        let Type::Named(named) = &ty else {
+            return None;
+        };
+        let paths = named.paths_to_sub_ty(decl, state).ok()?;
+        let super_ = Type::Named(decl.clone());
+        let candidates = ty.candidates(paths, state, |ty, state| {
+            ty.unify(&super_, state).then(Vec::new)
+        });
+        let [candidate] = candidates.as_slice() else {
+            return None;
+        };
+        if !infer && !candidate.bindings.is_empty() {
+            return None;
+        }
+        let (_, normalized) = candidate.assoc.iter().find(|(assoc, _)| assoc == name)?;
+        let normalized = normalized.clone();
+        info!("Normalized {} to {}", self, normalized);
+        let logic = candidate.commit(state);
+        Some((normalized, vec![inner, logic].into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::TypeError, logic::Logic, parser::parse_test};
+
+    #[test]
+    fn test_unknown_assoc() {
+        let test = parse_test(
+            "
+            Int;
+            List[T];
+            Iterator { type Item };
+            impl [T] Iterator for List[T] { type Item = T };
+            ---
+            <List[Int] as Iterator>::Key = Int;
+            ---
+            false
+            ",
+        );
+        let mut ts = test.ts;
+        let goals = Logic::from(test.goals);
+        assert_eq!(goals.reduce_to_fixpoint(&mut ts, true), Logic::False);
+        assert!(matches!(
+            &ts.errors[..],
+            [TypeError::UnknownAssoc { decl, name }] if decl == "Iterator" && name == "Key"
+        ));
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+
+use tracing::info;
+
+use crate::{
+    logic::{explain::Why, Logic},
+    state::TypeSystem,
+};
+
+use super::{bound::Bound, impl_::Impl, Type};
+
+/// A path of impls which applies to a type, along with what must hold for it to be used.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub path: Vec<Impl>,
+    /// Bindings learnt for type vars which existed before the path was followed.
+    pub bindings: Vec<(u32, Type)>,
+    pub bounds: Vec<Bound>,
+    /// The associated types bound by the last impl on the path.
+    pub assoc: Vec<(String, Type)>,
+}
+
+impl Candidate {
+    /// Everything needed for the candidate to hold, without binding anything.
+    pub fn logic(&self) -> Logic {
+        self.bindings
+            .iter()
+            .map(|(id, ty)| Logic::binding(*id, ty.clone()))
+            .chain(self.bounds.iter().cloned().map(Logic::from))
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /// Commits to the candidate, binding its type vars and leaving just its bounds to prove.
+    pub fn commit(&self, state: &mut TypeSystem) -> Logic {
+        info!(
+            "Selected impls [{}]",
+            self.path
+                .iter()
+                .map(Impl::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        for (id, ty) in &self.bindings {
+            state.type_vars.insert(*id, ty.clone());
+        }
+        self.bounds
+            .iter()
+            .cloned()
+            .map(Logic::from)
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /// Drops every candidate whose first impl is less specific than another's.
+    ///
+    /// Only candidates which apply without binding anything can win, so an
+    /// unknown type is never narrowed just because a specific impl exists for it.
+    fn most_specific(candidates: Vec<Candidate>, state: &mut TypeSystem) -> Vec<Candidate> {
+        let winners = candidates
+            .iter()
+            .filter(|candidate| candidate.bindings.is_empty())
+            .filter_map(|candidate| candidate.path.first().cloned())
+            .collect::<Vec<_>>();
+        let mut res = vec![];
+        for candidate in candidates {
+            let dominated = candidate.path.first().is_some_and(|first| {
+                winners
+                    .iter()
+                    .any(|winner| winner.is_more_specific(first, state))
+            });
+            if dominated {
+                info!("Specialized away [{}]", candidate.path[0]);
+            } else {
+                res.push(candidate);
+            }
+        }
+        res
+    }
+
+    /// Selects the only candidate when there is one, otherwise leaves a choice between them.
+    pub fn select(candidates: Vec<Candidate>, state: &mut TypeSystem, infer: bool) -> Logic {
+        match candidates.len() {
+            0 => Logic::False,
+            1 if infer => candidates[0].commit(state),
+            1 => candidates[0].logic(),
+            _ => Logic::OneOf(candidates.iter().map(Candidate::logic).collect()),
+        }
+    }
+}
+
+/// The where-clauses and associated types picked up by following a path.
+type Followed = (Vec<Bound>, Vec<(String, Type)>);
+
+impl Type {
+    /// Follows each path of impls from this type, unifying every impl head with
+    /// the type reached so far, and keeps the paths for which `finish` also
+    /// unifies at the end. Any bounds `finish` leaves are added to the candidate.
+    ///
+    /// Nothing is committed to `state`, the bindings learnt along each path are
+    /// kept on its candidate instead.
+    pub fn candidates(
+        &self,
+        paths: Vec<Vec<Impl>>,
+        state: &mut TypeSystem,
+        mut finish: impl FnMut(&Type, &mut TypeSystem) -> Option<Vec<Bound>>,
+    ) -> Vec<Candidate> {
+        let mut candidates = vec![];
+        for path in paths {
+            let snapshot = state.snapshot();
+            match self.follow(&path, state, &mut finish) {
+                Ok((bounds, assoc)) => candidates.push(Candidate {
+                    path,
+                    bindings: state.bindings_since(&snapshot),
+                    bounds: bounds.iter().map(|b| b.resolve(state)).collect(),
+                    assoc: assoc
+                        .iter()
+                        .map(|(name, ty)| (name.clone(), ty.resolve(state)))
+                        .collect(),
+                }),
+                Err(why) => {
+                    if let Some(trace) = &mut state.trace {
+                        trace.reject(path, why);
+                    }
+                }
+            }
+            state.restore(snapshot);
+        }
+        // Vars an impl introduced which nothing bound are still mentioned by its candidate.
+        state.revive(candidates.iter().flat_map(|candidate| {
+            let mut vars = candidate.logic().vars();
+            for (_, ty) in &candidate.assoc {
+                vars.extend(ty.vars());
+            }
+            vars
+        }));
+        if state.specialize {
+            candidates = Candidate::most_specific(candidates, state);
+        }
+        if let Some(trace) = &mut state.trace {
+            trace.candidates(&candidates);
+        }
+        candidates
+    }
+
+    fn follow(
+        &self,
+        path: &[Impl],
+        state: &mut TypeSystem,
+        finish: &mut impl FnMut(&Type, &mut TypeSystem) -> Option<Vec<Bound>>,
+    ) -> Result<Followed, Why> {
+        let mut ty = self.clone();
+        let mut bounds = vec![];
+        let mut assoc = vec![];
+        for impl_ in path {
+            let reached = ty.resolve(state);
+            let fresh = impl_.map(&ty, state).ok_or_else(|| Why::Head {
+                impl_: Box::new(impl_.clone()),
+                ty: reached,
+            })?;
+            bounds.extend(fresh.bounds);
+            assoc = fresh.assoc;
+            ty = Type::Named(fresh.to);
+        }
+        let reached = ty.resolve(state);
+        bounds.extend(finish(&ty, state).ok_or(Why::Target(reached))?);
+        Ok((bounds, assoc))
+    }
+}
+
+impl TypeSystem {
+    /// The vars which were free in `snapshot` but have been bound since.
+    pub fn bindings_since(&self, snapshot: &HashMap<u32, Type>) -> Vec<(u32, Type)> {
+        let mut bindings = snapshot
+            .iter()
+            .filter(|(id, ty)| **ty == Type::Free && self.resolve(**id).is_some())
+            .map(|(id, _)| (*id, Type::Var(*id).resolve(self)))
+            .collect::<Vec<_>>();
+        bindings.sort_by_key(|(id, _)| *id);
+        bindings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{
+        logic::{stmt::Stmt, Logic},
+        parser::parse_test,
+    };
+
+    const INPUT: &str = "
+        Int;
+        List[T];
+        Display;
+        impl [T] Display for List[T] where T: Display;
+        impl [] Display for List[Int];
+        ---
+        List[Int]: Display;
+        ---
+        true
+    ";
+
+    fn reduce(specialize: bool) -> Logic {
+        let test = parse_test(INPUT);
+        let mut ts = test.ts;
+        ts.specialize = specialize;
+        let Some(Logic::Stmt(Stmt::Extends { sub, super_ })) = test.goals.first() else {
+            panic!("Expected Bound");
+        };
+        sub.is_bound_by(super_, &mut ts, true)
+    }
+
+    #[test]
+    fn test_ambiguous() {
+        assert!(matches!(reduce(false), Logic::OneOf(_)));
+    }
+
+    #[test]
+    fn test_rejected_vars_are_dropped() {
+        let test = parse_test(INPUT);
+        let mut ts = test.ts;
+        let before = ts.type_vars.len();
+        let Some(Logic::Stmt(Stmt::Extends { sub, super_ })) = test.goals.first() else {
+            panic!("Expected Bound");
+        };
+        for _ in 0..3 {
+            sub.is_bound_by(super_, &mut ts, false);
+        }
+        // Every var the impls were freshened with got bound, so none are kept.
+        assert_eq!(ts.type_vars.len(), before);
+    }
+
+    #[test]
+    fn test_specialized() {
+        assert_eq!(reduce(true), Logic::True);
+    }
+}
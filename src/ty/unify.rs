@@ -0,0 +1,142 @@
+use crate::state::TypeSystem;
+
+use super::{Named, Type};
+
+impl Type {
+    /// Unifies two types, binding free type vars on either side in `state`.
+    ///
+    /// Bindings are written straight into `state.type_vars`, so callers which may
+    /// need to back out should take a snapshot first.
+    pub fn unify(&self, other: &Type, state: &mut TypeSystem) -> bool {
+        match (self, other) {
+            (Type::Var(first), Type::Var(second)) if first == second => true,
+            (Type::Var(id), _) if state.resolve(*id).is_some() => {
+                state.resolve(*id).unwrap().unify(other, state)
+            }
+            (_, Type::Var(id)) if state.resolve(*id).is_some() => {
+                self.unify(&state.resolve(*id).unwrap(), state)
+            }
+            (Type::Applied(id, args), _) if state.resolve(*id).is_some() => {
+                match Type::Var(*id).resolve(state).apply(args.clone()) {
+                    Some(ty) => ty.unify(other, state),
+                    None => false,
+                }
+            }
+            (_, Type::Applied(id, args)) if state.resolve(*id).is_some() => {
+                match Type::Var(*id).resolve(state).apply(args.clone()) {
+                    Some(ty) => self.unify(&ty, state),
+                    None => false,
+                }
+            }
+            // Always bind the newer var to the older one, so fresh vars from impls
+            // never leak into the bindings of type vars that already existed.
+            (Type::Var(first), Type::Var(second)) => {
+                let (old, new) = if first < second {
+                    (first, second)
+                } else {
+                    (second, first)
+                };
+                state.type_vars.insert(*new, Type::Var(*old));
+                true
+            }
+            (Type::Var(id), ty) | (ty, Type::Var(id)) => {
+                if ty.occurs(*id, state) {
+                    return false;
+                }
+                state.type_vars.insert(*id, ty.clone());
+                true
+            }
+            (Type::Unknown, _) | (_, Type::Unknown) => true,
+            (Type::Applied(first, args), Type::Applied(second, other_args)) => {
+                Type::Var(*first).unify(&Type::Var(*second), state)
+                    && Type::unify_all(args, other_args, state)
+            }
+            // A constructor var applied to arguments is a named type or generic
+            // applied to as many, which binds the var to the bare constructor.
+            (Type::Applied(id, args), Type::Named(named))
+            | (Type::Named(named), Type::Applied(id, args)) => {
+                let ctor = Type::Named(Named {
+                    name: named.name.clone(),
+                    args: vec![],
+                });
+                args.len() == named.args.len()
+                    && Type::Var(*id).unify(&ctor, state)
+                    && Type::unify_all(args, &named.args, state)
+            }
+            (Type::Applied(id, args), Type::Generic(name, other_args))
+            | (Type::Generic(name, other_args), Type::Applied(id, args)) => {
+                let ctor = Type::Generic(name.clone(), vec![]);
+                args.len() == other_args.len()
+                    && Type::Var(*id).unify(&ctor, state)
+                    && Type::unify_all(args, other_args, state)
+            }
+            (Type::Alias { ty, .. }, _) => ty.unify(other, state),
+            (_, Type::Alias { ty, .. }) => self.unify(ty, state),
+            (Type::Named(this), Type::Named(other)) => this.unify(other, state),
+            (Type::Generic(first, args), Type::Generic(second, other_args)) => {
+                first == second && Type::unify_all(args, other_args, state)
+            }
+            (Type::Any, Type::Any) | (Type::Never, Type::Never) => true,
+            (
+                Type::Function { params, ret },
+                Type::Function {
+                    params: other_params,
+                    ret: other_ret,
+                },
+            ) => Type::unify_all(params, other_params, state) && ret.unify(other_ret, state),
+            (Type::Tuple(tys), Type::Tuple(others))
+            | (Type::Union(tys), Type::Union(others))
+            | (Type::Intersection(tys), Type::Intersection(others)) => {
+                Type::unify_all(tys, others, state)
+            }
+            (
+                Type::Projection { ty, decl, name },
+                Type::Projection {
+                    ty: other_ty,
+                    decl: other_decl,
+                    name: other_name,
+                },
+            ) => name == other_name && decl.unify(other_decl, state) && ty.unify(other_ty, state),
+            _ => false,
+        }
+    }
+
+    fn occurs(&self, var: u32, state: &TypeSystem) -> bool {
+        match self {
+            Type::Var(id) if *id == var => true,
+            Type::Var(id) => state.resolve(*id).is_some_and(|ty| ty.occurs(var, state)),
+            Type::Named(named) => named.args.iter().any(|arg| arg.occurs(var, state)),
+            Type::Projection { ty, decl, .. } => {
+                ty.occurs(var, state) || decl.args.iter().any(|arg| arg.occurs(var, state))
+            }
+            Type::Function { params, ret } => {
+                params.iter().any(|param| param.occurs(var, state)) || ret.occurs(var, state)
+            }
+            Type::Alias { ty, .. } => ty.occurs(var, state),
+            Type::Generic(_, args) => args.iter().any(|arg| arg.occurs(var, state)),
+            Type::Applied(id, args) => {
+                Type::Var(*id).occurs(var, state) || args.iter().any(|arg| arg.occurs(var, state))
+            }
+            Type::Tuple(tys) | Type::Union(tys) | Type::Intersection(tys) => {
+                tys.iter().any(|ty| ty.occurs(var, state))
+            }
+            _ => false,
+        }
+    }
+
+    fn unify_all(tys: &[Type], others: &[Type], state: &mut TypeSystem) -> bool {
+        tys.len() == others.len() && tys.iter().zip(others).all(|(a, b)| a.unify(b, state))
+    }
+}
+
+impl Named {
+    pub fn unify(&self, other: &Named, state: &mut TypeSystem) -> bool {
+        if self.name != other.name || self.args.len() != other.args.len() {
+            return false;
+        }
+        self.args
+            .iter()
+            .zip(other.args.iter())
+            .all(|(a, b)| a.unify(b, state))
+    }
+}
@@ -0,0 +1,65 @@
+use std::fmt::Display;
+
+use crate::state::TypeSystem;
+
+use super::{bound::Bound, Type};
+
+/// How subtyping on a decl's argument carries over to the decl.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variance {
+    /// `+T`: `D[A]: D[B]` when `A: B`.
+    Covariant,
+    /// `-T`: `D[A]: D[B]` when `B: A`.
+    Contravariant,
+    /// `T`: `D[A]: D[B]` only when `A = B`.
+    Invariant,
+}
+
+impl Display for Variance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Variance::Covariant => write!(f, "+"),
+            Variance::Contravariant => write!(f, "-"),
+            Variance::Invariant => Ok(()),
+        }
+    }
+}
+
+impl Type {
+    /// Unifies the type at the end of a path with the super type it should reach.
+    ///
+    /// Invariant arguments are unified, while each variant argument is left as a
+    /// bound to be proven along with the rest of the path.
+    pub fn unify_variant(&self, other: &Type, state: &mut TypeSystem) -> Option<Vec<Bound>> {
+        let (this, that) = (self.resolve(state), other.resolve(state));
+        let (Type::Named(this), Type::Named(other)) = (this.unalias(), that.unalias()) else {
+            return self.unify(other, state).then(Vec::new);
+        };
+        if this.name != other.name || this.args.len() != other.args.len() {
+            return None;
+        }
+        let decl = state.decls.get(&this.name).cloned();
+        let mut bounds = vec![];
+        for (index, (sub, super_)) in this.args.iter().zip(&other.args).enumerate() {
+            let variance = decl
+                .as_ref()
+                .map_or(Variance::Invariant, |decl| decl.variance(index));
+            match variance {
+                Variance::Invariant => {
+                    if !sub.unify(super_, state) {
+                        return None;
+                    }
+                }
+                Variance::Covariant => bounds.push(Bound {
+                    sub: sub.clone(),
+                    super_: super_.clone(),
+                }),
+                Variance::Contravariant => bounds.push(Bound {
+                    sub: super_.clone(),
+                    super_: sub.clone(),
+                }),
+            }
+        }
+        Some(bounds)
+    }
+}